@@ -0,0 +1,151 @@
+use crate::{OperationType, PolishNotationToken, TokenError};
+
+const TAG_OPERATION: u8 = 0;
+const TAG_NUMBER: u8 = 1;
+const TAG_SPACE: u8 = 2;
+
+/// Encodes tokens as a 1-byte tag followed by the payload: an operator byte
+/// for operations, a little-endian `u32` for numbers and nothing for spaces.
+pub fn serialize_tokens_to_binary(tokens: &[PolishNotationToken]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(tokens.len() * 2);
+
+    for token in tokens {
+        match token {
+            PolishNotationToken::Operation(op) => {
+                data.push(TAG_OPERATION);
+                data.push(operation_code(*op));
+            }
+            PolishNotationToken::Number(num) => {
+                data.push(TAG_NUMBER);
+                data.extend_from_slice(&num.to_le_bytes());
+            }
+            PolishNotationToken::Space => data.push(TAG_SPACE),
+        }
+    }
+
+    data
+}
+
+/// Decodes data produced by [`serialize_tokens_to_binary`].
+pub fn deserialize_tokens_from_binary(data: &[u8]) -> Result<Vec<PolishNotationToken>, TokenError> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let tag_offset = offset;
+        offset += 1;
+
+        match data[tag_offset] {
+            TAG_OPERATION => {
+                let op = data
+                    .get(offset)
+                    .and_then(|code| operation_from_code(*code))
+                    .ok_or(TokenError::InvalidBinaryData(offset))?;
+
+                tokens.push(PolishNotationToken::Operation(op));
+                offset += 1;
+            }
+            TAG_NUMBER => {
+                let bytes: [u8; 4] = data
+                    .get(offset..offset + 4)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or(TokenError::InvalidBinaryData(offset))?;
+
+                tokens.push(PolishNotationToken::Number(u32::from_le_bytes(bytes)));
+                offset += 4;
+            }
+            TAG_SPACE => tokens.push(PolishNotationToken::Space),
+            _ => return Err(TokenError::InvalidBinaryData(tag_offset)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn operation_code(op: OperationType) -> u8 {
+    match op {
+        OperationType::Addition => 0,
+        OperationType::Subtraction => 1,
+        OperationType::Multiplication => 2,
+        OperationType::Division => 3,
+    }
+}
+
+fn operation_from_code(code: u8) -> Option<OperationType> {
+    match code {
+        0 => Some(OperationType::Addition),
+        1 => Some(OperationType::Subtraction),
+        2 => Some(OperationType::Multiplication),
+        3 => Some(OperationType::Division),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_every_token_kind() {
+        let tokens = vec![
+            PolishNotationToken::Number(3),
+            PolishNotationToken::Space,
+            PolishNotationToken::Number(u32::MAX),
+            PolishNotationToken::Operation(OperationType::Addition),
+            PolishNotationToken::Operation(OperationType::Subtraction),
+            PolishNotationToken::Operation(OperationType::Multiplication),
+            PolishNotationToken::Operation(OperationType::Division),
+        ];
+
+        let data = serialize_tokens_to_binary(&tokens);
+        assert_eq!(deserialize_tokens_from_binary(&data), Ok(tokens));
+    }
+
+    #[test]
+    fn uses_tag_and_little_endian_payload() {
+        let tokens = [
+            PolishNotationToken::Number(258),
+            PolishNotationToken::Space,
+            PolishNotationToken::Operation(OperationType::Division),
+        ];
+
+        assert_eq!(
+            serialize_tokens_to_binary(&tokens),
+            vec![TAG_NUMBER, 2, 1, 0, 0, TAG_SPACE, TAG_OPERATION, 3]
+        );
+    }
+
+    #[test]
+    fn empty_input_is_empty_output() {
+        assert!(serialize_tokens_to_binary(&[]).is_empty());
+        assert_eq!(deserialize_tokens_from_binary(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn rejects_unknown_tags() {
+        assert_eq!(
+            deserialize_tokens_from_binary(&[TAG_SPACE, 9]),
+            Err(TokenError::InvalidBinaryData(1))
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_payloads() {
+        assert_eq!(
+            deserialize_tokens_from_binary(&[TAG_NUMBER, 1, 0]),
+            Err(TokenError::InvalidBinaryData(1))
+        );
+        assert_eq!(
+            deserialize_tokens_from_binary(&[TAG_OPERATION]),
+            Err(TokenError::InvalidBinaryData(1))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_operation_codes() {
+        assert_eq!(
+            deserialize_tokens_from_binary(&[TAG_OPERATION, 4]),
+            Err(TokenError::InvalidBinaryData(1))
+        );
+    }
+}
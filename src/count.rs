@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use crate::PolishNotationToken;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TokenKind {
+    Operation,
+    Number,
+    Space,
+}
+
+impl From<&PolishNotationToken> for TokenKind {
+    fn from(token: &PolishNotationToken) -> Self {
+        match token {
+            PolishNotationToken::Operation(_) => TokenKind::Operation,
+            PolishNotationToken::Number(_) => TokenKind::Number,
+            PolishNotationToken::Space => TokenKind::Space,
+        }
+    }
+}
+
+/// Counts `tokens` by kind. Kinds that do not occur are left out of the map.
+pub fn token_count_by_type(tokens: &[(usize, PolishNotationToken)]) -> HashMap<TokenKind, usize> {
+    let mut counts = HashMap::new();
+
+    for (_, token) in tokens {
+        *counts.entry(TokenKind::from(token)).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tokenize, TokenizerMode};
+
+    #[test]
+    fn counts_each_kind() {
+        let counts = token_count_by_type(&tokenize("3 5 + 2 *", TokenizerMode::Standard).unwrap());
+
+        assert_eq!(
+            counts,
+            HashMap::from([
+                (TokenKind::Number, 3),
+                (TokenKind::Operation, 2),
+                (TokenKind::Space, 4),
+            ])
+        );
+    }
+
+    #[test]
+    fn empty_input_has_no_counts() {
+        assert!(token_count_by_type(&[]).is_empty());
+    }
+
+    #[test]
+    fn missing_kinds_are_left_out() {
+        let counts = token_count_by_type(&tokenize("35", TokenizerMode::Standard).unwrap());
+
+        assert_eq!(counts, HashMap::from([(TokenKind::Number, 2)]));
+    }
+
+    #[test]
+    fn aliases_count_as_operations() {
+        let counts = token_count_by_type(&tokenize("34x5X", TokenizerMode::Standard).unwrap());
+
+        assert_eq!(counts.get(&TokenKind::Operation), Some(&2));
+    }
+
+    #[test]
+    fn kind_of_token() {
+        assert_eq!(
+            TokenKind::from(&PolishNotationToken::Number(7)),
+            TokenKind::Number
+        );
+        assert_eq!(
+            TokenKind::from(&PolishNotationToken::Space),
+            TokenKind::Space
+        );
+        assert_eq!(
+            TokenKind::from(&PolishNotationToken::Operation(
+                crate::OperationType::Division
+            )),
+            TokenKind::Operation
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan};
+
+use crate::{CalculationError, RpdError, TokenError};
+
+// Positions in the errors are byte offsets into the source, which is what
+// labels expect.
+
+impl Diagnostic for TokenError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            TokenError::InvalidCharacter(..) => Some(Box::new("rpd::invalid_character")),
+            TokenError::InvalidBinaryData(_) => Some(Box::new("rpd::invalid_binary_data")),
+            TokenError::ReadError(..) => Some(Box::new("rpd::read_error")),
+            TokenError::MultipleErrors(_) => Some(Box::new("rpd::multiple_errors")),
+            TokenError::UnknownOperator(_) => Some(Box::new("rpd::unknown_operator")),
+            TokenError::InvalidNumber(_) => Some(Box::new("rpd::invalid_number")),
+        }
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            TokenError::InvalidCharacter(..) => Some(Box::new(
+                "expressions may only contain digits, spaces and the operators + - * x X /",
+            )),
+            TokenError::UnknownOperator(_) => Some(Box::new("the operators are + - * x X and /")),
+            TokenError::InvalidNumber(_) => Some(Box::new(
+                "numbers are single digits; build larger ones with operators, e.g. \"4 3 *\"",
+            )),
+            TokenError::InvalidBinaryData(_)
+            | TokenError::ReadError(..)
+            | TokenError::MultipleErrors(_) => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        match self {
+            TokenError::InvalidCharacter(index, ch) => {
+                Some(Box::new(std::iter::once(LabeledSpan::new(
+                    Some(String::from("invalid character")),
+                    *index,
+                    ch.len_utf8(),
+                ))))
+            }
+            // Each collected error carries its own label and is reported through
+            // related() instead.
+            TokenError::InvalidBinaryData(_)
+            | TokenError::ReadError(..)
+            | TokenError::MultipleErrors(_)
+            | TokenError::UnknownOperator(_)
+            | TokenError::InvalidNumber(_) => None,
+        }
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        match self {
+            TokenError::MultipleErrors(errors) => {
+                Some(Box::new(errors.iter().map(|err| err as &dyn Diagnostic)))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Diagnostic for CalculationError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self {
+            CalculationError::NoNumberFoundForOperation(..) => "rpd::missing_operand",
+            CalculationError::NoResultAvailable(_) => "rpd::no_result",
+            CalculationError::IncompleteExpression(_) => "rpd::incomplete_expression",
+            CalculationError::DivisionByZero(_) => "rpd::division_by_zero",
+            CalculationError::Overflow(..) => "rpd::overflow",
+        };
+
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = match self {
+            CalculationError::NoNumberFoundForOperation(..) => {
+                "operators take the two numbers before them, e.g. \"3 5 +\""
+            }
+            CalculationError::NoResultAvailable(_) => "enter at least one number",
+            CalculationError::IncompleteExpression(_) => {
+                "add operators until a single number is left on the stack"
+            }
+            CalculationError::DivisionByZero(_) => return None,
+            CalculationError::Overflow(..) => {
+                "the result does not fit in the numbers the evaluator works with"
+            }
+        };
+
+        Some(Box::new(help))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let (pos, label) = match self {
+            CalculationError::NoNumberFoundForOperation(pos, _) => (*pos, "missing operand"),
+            CalculationError::DivisionByZero(pos) => (*pos, "divides by zero"),
+            CalculationError::Overflow(pos, _) => (*pos, "out of range"),
+            CalculationError::NoResultAvailable(_) | CalculationError::IncompleteExpression(_) => {
+                return None
+            }
+        };
+
+        Some(Box::new(std::iter::once(LabeledSpan::new(
+            Some(String::from(label)),
+            pos,
+            1,
+        ))))
+    }
+}
+
+// RpdError only says which stage failed, so everything comes from the error
+// it wraps.
+impl Diagnostic for RpdError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.inner().code()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.inner().help()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.inner().labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.inner().related()
+    }
+}
+
+impl RpdError {
+    fn inner(&self) -> &dyn Diagnostic {
+        match self {
+            RpdError::Token(err) => err,
+            RpdError::Calculation(err) => err,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_rpd, tokenize, tokenize_collecting_errors, TokenizerMode};
+
+    fn label_spans(diagnostic: &dyn Diagnostic) -> Vec<(usize, usize)> {
+        diagnostic
+            .labels()
+            .into_iter()
+            .flatten()
+            .map(|label| (label.offset(), label.len()))
+            .collect()
+    }
+
+    #[test]
+    fn invalid_character_labels_the_character() {
+        let err = tokenize("3 5 ?", TokenizerMode::Standard).unwrap_err();
+
+        assert_eq!(err.code().unwrap().to_string(), "rpd::invalid_character");
+        assert_eq!(label_spans(&err), vec![(4, 1)]);
+    }
+
+    #[test]
+    fn missing_operand_labels_the_operator() {
+        let err = calculate_rpd(tokenize("3 +", TokenizerMode::Standard).unwrap()).unwrap_err();
+
+        assert_eq!(err.code().unwrap().to_string(), "rpd::missing_operand");
+        assert_eq!(label_spans(&err), vec![(2, 1)]);
+        assert!(err.help().is_some());
+    }
+
+    #[test]
+    fn arithmetic_errors_label_the_operator() {
+        let err = calculate_rpd(tokenize("3 0 /", TokenizerMode::Standard).unwrap()).unwrap_err();
+        assert_eq!(label_spans(&err), vec![(4, 1)]);
+
+        let err = calculate_rpd(tokenize("1 2 -", TokenizerMode::Standard).unwrap()).unwrap_err();
+        assert_eq!(label_spans(&err), vec![(4, 1)]);
+    }
+
+    #[test]
+    fn evaluate_errors_keep_their_diagnostics() {
+        let err = crate::evaluate("3 ?").unwrap_err();
+        assert_eq!(err.code().unwrap().to_string(), "rpd::invalid_character");
+        assert!(err.help().is_some());
+        assert_eq!(label_spans(&err), vec![(2, 1)]);
+
+        let err = crate::evaluate("3 0 /").unwrap_err();
+        assert_eq!(err.code().unwrap().to_string(), "rpd::division_by_zero");
+        assert_eq!(label_spans(&err), vec![(4, 1)]);
+    }
+
+    #[test]
+    fn positionless_errors_have_no_labels() {
+        let incomplete = CalculationError::IncompleteExpression(2);
+        let binary = TokenError::InvalidBinaryData(0);
+
+        assert!(incomplete.labels().is_none());
+        assert!(binary.labels().is_none());
+    }
+
+    #[test]
+    fn multiple_errors_are_related_diagnostics() {
+        let err = tokenize_collecting_errors("a 3 b", TokenizerMode::Standard).unwrap_err();
+        let related: Vec<_> = err.related().unwrap().collect();
+
+        assert_eq!(err.code().unwrap().to_string(), "rpd::multiple_errors");
+        assert_eq!(related.len(), 2);
+        assert_eq!(label_spans(related[1]), vec![(4, 1)]);
+
+        let err = tokenize_collecting_errors("é a", TokenizerMode::Standard).unwrap_err();
+        let related: Vec<_> = err.related().unwrap().collect();
+        assert_eq!(label_spans(related[0]), vec![(0, 2)]);
+        assert_eq!(label_spans(related[1]), vec![(3, 1)]);
+    }
+
+    #[test]
+    fn every_error_has_a_code() {
+        let token_errors = [
+            TokenError::InvalidCharacter(0, 'a'),
+            TokenError::InvalidBinaryData(0),
+            TokenError::ReadError(0, std::io::ErrorKind::Other),
+            TokenError::MultipleErrors(vec![]),
+            TokenError::UnknownOperator(String::from("%")),
+            TokenError::InvalidNumber(12),
+        ];
+
+        for err in &token_errors {
+            assert!(err.code().is_some());
+        }
+
+        let calculation_errors = [
+            CalculationError::NoNumberFoundForOperation(0, crate::OperationType::Addition),
+            CalculationError::NoResultAvailable("No result can be generated."),
+            CalculationError::IncompleteExpression(2),
+            CalculationError::DivisionByZero(0),
+            CalculationError::Overflow(0, crate::OperationType::Addition),
+        ];
+
+        for err in &calculation_errors {
+            assert!(err.code().is_some());
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::PolishNotationToken;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TokenDiff {
+    Unchanged(PolishNotationToken),
+    Added(PolishNotationToken),
+    Removed(PolishNotationToken),
+}
+
+/// Computes a minimal edit script between two token lists using their
+/// longest common subsequence.
+pub fn token_diff(before: &[PolishNotationToken], after: &[PolishNotationToken]) -> Vec<TokenDiff> {
+    // lcs[i][j] is the length of the longest common subsequence of before[i..] and after[j..].
+    let mut lcs = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lcs[i][j] = if before[i] == after[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(before.len().max(after.len()));
+    let (mut i, mut j) = (0, 0);
+
+    while i < before.len() && j < after.len() {
+        if before[i] == after[j] {
+            diff.push(TokenDiff::Unchanged(before[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(TokenDiff::Removed(before[i]));
+            i += 1;
+        } else {
+            diff.push(TokenDiff::Added(after[j]));
+            j += 1;
+        }
+    }
+
+    diff.extend(before[i..].iter().map(|token| TokenDiff::Removed(*token)));
+    diff.extend(after[j..].iter().map(|token| TokenDiff::Added(*token)));
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{num, ADD, MUL};
+
+    #[test]
+    fn identical_lists_are_unchanged() {
+        let tokens = [num(3), num(5), ADD];
+
+        assert_eq!(
+            token_diff(&tokens, &tokens),
+            vec![
+                TokenDiff::Unchanged(num(3)),
+                TokenDiff::Unchanged(num(5)),
+                TokenDiff::Unchanged(ADD),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_appended_tokens() {
+        assert_eq!(
+            token_diff(&[num(3)], &[num(3), num(2), MUL]),
+            vec![
+                TokenDiff::Unchanged(num(3)),
+                TokenDiff::Added(num(2)),
+                TokenDiff::Added(MUL),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_removed_tokens() {
+        assert_eq!(
+            token_diff(&[num(3), num(0), ADD], &[num(3)]),
+            vec![
+                TokenDiff::Unchanged(num(3)),
+                TokenDiff::Removed(num(0)),
+                TokenDiff::Removed(ADD),
+            ]
+        );
+    }
+
+    #[test]
+    fn replacement_is_a_removal_and_an_addition() {
+        assert_eq!(
+            token_diff(&[num(3), num(5), ADD], &[num(3), num(5), MUL]),
+            vec![
+                TokenDiff::Unchanged(num(3)),
+                TokenDiff::Unchanged(num(5)),
+                TokenDiff::Removed(ADD),
+                TokenDiff::Added(MUL),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_the_longest_common_subsequence() {
+        let diff = token_diff(&[num(1), num(2), num(3), num(4)], &[num(2), num(4), num(5)]);
+        let unchanged: Vec<_> = diff
+            .iter()
+            .filter(|entry| matches!(entry, TokenDiff::Unchanged(_)))
+            .collect();
+
+        assert_eq!(
+            unchanged,
+            vec![&TokenDiff::Unchanged(num(2)), &TokenDiff::Unchanged(num(4))]
+        );
+    }
+
+    #[test]
+    fn empty_lists() {
+        assert_eq!(token_diff(&[], &[]), vec![]);
+        assert_eq!(token_diff(&[], &[num(1)]), vec![TokenDiff::Added(num(1))]);
+        assert_eq!(token_diff(&[num(1)], &[]), vec![TokenDiff::Removed(num(1))]);
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt::Display;
+
+use crate::{calculate_rpd, CalculationError, OperationType, PolishNotationToken, TokenError};
+
+/// A token list built in code rather than parsed from a string.
+///
+/// Tokens are separated by spaces, the same way [`crate::tokenize`] returns
+/// them for `"3 5 +"`, so error positions match the written-out expression.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Expression(Vec<PolishNotationToken>);
+
+impl Expression {
+    pub fn new() -> Self {
+        Expression(Vec::new())
+    }
+
+    /// Appends a number. Only single digits can be typed as one token, so
+    /// anything above 9 is rejected.
+    pub fn push_num(self, num: u32) -> Result<Self, TokenError> {
+        if num > 9 {
+            return Err(TokenError::InvalidNumber(num));
+        }
+
+        Ok(self.push(PolishNotationToken::Number(num)))
+    }
+
+    pub fn push_op(self, op: OperationType) -> Self {
+        self.push(PolishNotationToken::Operation(op))
+    }
+
+    pub fn build(self) -> Self {
+        self
+    }
+
+    pub fn as_slice(&self) -> &[PolishNotationToken] {
+        &self.0
+    }
+
+    fn push(mut self, token: PolishNotationToken) -> Self {
+        if !self.0.is_empty() {
+            self.0.push(PolishNotationToken::Space);
+        }
+        self.0.push(token);
+        self
+    }
+}
+
+/// Writes the expression the way it would be typed, e.g. `3 5 +`. Every
+/// number is a single digit, so the output tokenizes back to the same tokens.
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for token in &self.0 {
+            match token {
+                PolishNotationToken::Operation(op) => write!(f, "{}", op)?,
+                PolishNotationToken::Number(num) => write!(f, "{}", num)?,
+                PolishNotationToken::Space => write!(f, " ")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn evaluate_expression(expression: &Expression) -> Result<u32, CalculationError> {
+    calculate_rpd(expression.0.iter().copied().enumerate())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tokenize, TokenizerMode};
+
+    fn tokens_of(expression: &Expression) -> Vec<(usize, PolishNotationToken)> {
+        expression.as_slice().iter().copied().enumerate().collect()
+    }
+
+    #[test]
+    fn builds_and_evaluates() -> Result<(), TokenError> {
+        let expression = Expression::new()
+            .push_num(3)?
+            .push_num(5)?
+            .push_op(OperationType::Addition)
+            .build();
+
+        assert_eq!(evaluate_expression(&expression), Ok(8));
+
+        Ok(())
+    }
+
+    #[test]
+    fn matches_tokenized_string() -> Result<(), TokenError> {
+        let expression = Expression::new()
+            .push_num(3)?
+            .push_num(5)?
+            .push_op(OperationType::Addition)
+            .push_num(2)?
+            .push_op(OperationType::Multiplication)
+            .build();
+
+        assert_eq!(
+            tokens_of(&expression),
+            tokenize("3 5 + 2 *", TokenizerMode::Standard).unwrap()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_expression_has_no_result() {
+        assert_eq!(Expression::new().build().as_slice(), &[]);
+        assert_eq!(
+            evaluate_expression(&Expression::new()),
+            Err(CalculationError::NoResultAvailable(
+                "No result can be generated."
+            ))
+        );
+    }
+
+    #[test]
+    fn errors_point_into_the_expression() -> Result<(), TokenError> {
+        let expression = Expression::new()
+            .push_num(3)?
+            .push_num(0)?
+            .push_op(OperationType::Division)
+            .build();
+
+        assert_eq!(
+            evaluate_expression(&expression),
+            Err(CalculationError::DivisionByZero(4))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_multi_digit_numbers() {
+        assert_eq!(
+            Expression::new().push_num(12),
+            Err(TokenError::InvalidNumber(12))
+        );
+        assert!(Expression::new().push_num(9).is_ok());
+    }
+
+    #[test]
+    fn displays_canonical_form() -> Result<(), TokenError> {
+        let expression = Expression::new()
+            .push_num(3)?
+            .push_num(5)?
+            .push_op(OperationType::Addition)
+            .build();
+
+        assert_eq!(expression.to_string(), "3 5 +");
+        assert_eq!(Expression::new().to_string(), "");
+
+        Ok(())
+    }
+
+    #[test]
+    fn display_round_trips_through_tokenize() -> Result<(), TokenError> {
+        let expression = Expression::new()
+            .push_num(9)?
+            .push_num(8)?
+            .push_op(OperationType::Multiplication)
+            .push_num(3)?
+            .push_op(OperationType::Subtraction)
+            .build();
+
+        assert_eq!(expression.to_string(), "9 8 * 3 -");
+        assert_eq!(
+            tokenize(&expression.to_string(), TokenizerMode::Standard).unwrap(),
+            tokens_of(&expression)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{CalculationError, PolishNotationToken};
+
+/// Evaluates a fixed number of tokens on a stack allocated inline.
+///
+/// No token pushes more than one value, so `N` values is always enough
+/// room and the evaluation never touches the heap.
+pub fn evaluate_fixed<const N: usize>(
+    tokens: [(usize, PolishNotationToken); N],
+) -> Result<u32, CalculationError> {
+    let mut stack = [0u32; N];
+    let mut len = 0;
+
+    for (pos, token) in tokens {
+        match token {
+            PolishNotationToken::Operation(op) => {
+                if len < 2 {
+                    return Err(CalculationError::NoNumberFoundForOperation(pos, op));
+                }
+
+                stack[len - 2] = op.compute(pos, stack[len - 2], stack[len - 1])?;
+                len -= 1;
+            }
+            PolishNotationToken::Number(num) => {
+                stack[len] = num;
+                len += 1;
+            }
+            PolishNotationToken::Space => continue,
+        }
+    }
+
+    match len {
+        0 => Err(CalculationError::NoResultAvailable(
+            "No result can be generated.",
+        )),
+        1 => Ok(stack[0]),
+        _ => Err(CalculationError::IncompleteExpression(len)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{num, ADD, DIV};
+    use crate::OperationType;
+
+    #[test]
+    fn evaluates_expressions() {
+        assert_eq!(evaluate_fixed([(0, num(3)), (1, num(5)), (2, ADD)]), Ok(8));
+        assert_eq!(evaluate_fixed([(0, num(7))]), Ok(7));
+    }
+
+    #[test]
+    fn skips_spaces() {
+        assert_eq!(
+            evaluate_fixed([
+                (0, num(8)),
+                (1, PolishNotationToken::Space),
+                (2, num(2)),
+                (3, PolishNotationToken::Space),
+                (4, DIV),
+            ]),
+            Ok(4)
+        );
+    }
+
+    #[test]
+    fn reports_missing_operands() {
+        assert_eq!(
+            evaluate_fixed([(0, num(3)), (1, ADD)]),
+            Err(CalculationError::NoNumberFoundForOperation(
+                1,
+                OperationType::Addition
+            ))
+        );
+    }
+
+    #[test]
+    fn reports_empty_and_incomplete_expressions() {
+        assert_eq!(
+            evaluate_fixed::<0>([]),
+            Err(CalculationError::NoResultAvailable(
+                "No result can be generated."
+            ))
+        );
+        assert_eq!(
+            evaluate_fixed([(0, num(3)), (1, num(5))]),
+            Err(CalculationError::IncompleteExpression(2))
+        );
+    }
+
+    #[test]
+    fn reports_arithmetic_errors() {
+        assert_eq!(
+            evaluate_fixed([(0, num(3)), (1, num(0)), (2, DIV)]),
+            Err(CalculationError::DivisionByZero(2))
+        );
+        assert_eq!(
+            evaluate_fixed([(0, num(u32::MAX)), (1, num(1)), (2, ADD)]),
+            Err(CalculationError::Overflow(2, OperationType::Addition))
+        );
+    }
+
+    #[test]
+    fn matches_calculate_rpd() {
+        let tokens = [(0, num(9)), (1, num(3)), (2, DIV), (3, num(4)), (4, ADD)];
+        assert_eq!(evaluate_fixed(tokens), crate::calculate_rpd(tokens));
+    }
+}
@@ -0,0 +1,119 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Radix used for every value, between 2 and 36.
+    pub base: u32,
+    pub separator: String,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            base: 10,
+            separator: String::from(", "),
+        }
+    }
+}
+
+/// Formats stack values from bottom to top as a bracketed list, e.g.
+/// `[1, 2, 3]`.
+///
+/// # Panics
+///
+/// Panics if `opts.base` is not between 2 and 36.
+pub fn format_stack(stack: &[u32], opts: &FormatOptions) -> String {
+    assert!(
+        (2..=36).contains(&opts.base),
+        "base must be between 2 and 36, got {}",
+        opts.base
+    );
+
+    let values: Vec<String> = stack
+        .iter()
+        .map(|value| format_in_base(*value, opts.base))
+        .collect();
+
+    format!("[{}]", values.join(&opts.separator))
+}
+
+fn format_in_base(mut value: u32, base: u32) -> String {
+    let mut digits = Vec::new();
+
+    loop {
+        digits.push(char::from_digit(value % base, base).unwrap());
+        value /= base;
+
+        if value == 0 {
+            break;
+        }
+    }
+
+    digits.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_decimal_list_by_default() {
+        assert_eq!(
+            format_stack(&[1, 25, 3], &FormatOptions::default()),
+            "[1, 25, 3]"
+        );
+    }
+
+    #[test]
+    fn formats_empty_stack() {
+        assert_eq!(format_stack(&[], &FormatOptions::default()), "[]");
+    }
+
+    #[test]
+    fn respects_base() {
+        let hex = FormatOptions {
+            base: 16,
+            ..FormatOptions::default()
+        };
+        let binary = FormatOptions {
+            base: 2,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(format_stack(&[255, 16, 0], &hex), "[ff, 10, 0]");
+        assert_eq!(format_stack(&[5, 0], &binary), "[101, 0]");
+    }
+
+    #[test]
+    fn respects_separator() {
+        let opts = FormatOptions {
+            separator: String::from(" | "),
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(format_stack(&[1, 2], &opts), "[1 | 2]");
+    }
+
+    #[test]
+    fn formats_largest_value() {
+        let base36 = FormatOptions {
+            base: 36,
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            format_stack(&[u32::MAX], &FormatOptions::default()),
+            "[4294967295]"
+        );
+        assert_eq!(format_stack(&[u32::MAX], &base36), "[1z141z3]");
+    }
+
+    #[test]
+    #[should_panic(expected = "base must be between 2 and 36")]
+    fn panics_on_invalid_base() {
+        let opts = FormatOptions {
+            base: 1,
+            ..FormatOptions::default()
+        };
+
+        format_stack(&[1], &opts);
+    }
+}
@@ -0,0 +1,13 @@
+use crate::evaluate;
+
+/// Entry point for fuzz targets, e.g.
+/// `fuzz_target!(|data: &[u8]| rpd::fuzz_evaluate(data));`.
+///
+/// Any input is acceptable; errors are expected and ignored, and the only
+/// failure the fuzzer should find is a panic. Only compiled when building
+/// with `--cfg fuzzing`, which `cargo fuzz` sets.
+pub fn fuzz_evaluate(data: &[u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = evaluate(input);
+    }
+}
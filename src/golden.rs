@@ -0,0 +1,110 @@
+use crate::evaluate;
+
+const GOLDEN_TOLERANCE: f64 = 1e-10;
+
+/// Evaluates `input` and checks that the result is within `1e-10` of
+/// `expected`, describing the mismatch in the error otherwise.
+pub fn golden_test(input: &str, expected: f64) -> Result<(), String> {
+    let result = evaluate(input).map_err(|err| {
+        format!(
+            "golden test \"{}\" failed\n  expected: {}\n  error:    {}",
+            input, expected, err
+        )
+    })?;
+
+    let difference = (f64::from(result) - expected).abs();
+    if difference <= GOLDEN_TOLERANCE {
+        Ok(())
+    } else {
+        Err(format!(
+            "golden test \"{}\" failed\n  expected: {}\n  actual:   {}\n  diff:     {}",
+            input, expected, result, difference
+        ))
+    }
+}
+
+/// Runs [`golden_test`] for every `"input" => expected` pair and evaluates
+/// to a `Result<(), String>` listing all failures, not just the first one.
+#[macro_export]
+macro_rules! goldenfile {
+    ($($input:expr => $expected:expr),* $(,)?) => {{
+        let failures: ::std::vec::Vec<::std::string::String> = [
+            $($crate::golden_test($input, $expected)),*
+        ]
+        .into_iter()
+        .filter_map(::std::result::Result::err)
+        .collect();
+
+        if failures.is_empty() {
+            ::std::result::Result::Ok::<(), ::std::string::String>(())
+        } else {
+            ::std::result::Result::Err(failures.join("\n"))
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_on_exact_result() {
+        assert_eq!(golden_test("3 5 +", 8.0), Ok(()));
+    }
+
+    #[test]
+    fn passes_within_tolerance() {
+        assert_eq!(golden_test("9 3 /", 3.0 + 1e-11), Ok(()));
+    }
+
+    #[test]
+    fn reports_mismatch_with_diff() {
+        assert_eq!(
+            golden_test("9 2 /", 4.5),
+            Err(String::from(
+                "golden test \"9 2 /\" failed\n  expected: 4.5\n  actual:   4\n  diff:     0.5"
+            ))
+        );
+    }
+
+    #[test]
+    fn reports_evaluation_errors() {
+        assert_eq!(
+            golden_test("3 +", 3.0),
+            Err(String::from(
+                "golden test \"3 +\" failed\n  expected: 3\n  error:    No number found before the operation + at position 3"
+            ))
+        );
+    }
+
+    #[test]
+    fn goldenfile_passes_when_all_pairs_pass() {
+        assert_eq!(
+            crate::goldenfile! {
+                "3 5 +" => 8.0,
+                "2 3 x" => 6.0,
+            },
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn goldenfile_lists_every_failure() {
+        let failures = crate::goldenfile! {
+            "1 1 +" => 3.0,
+            "3 5 +" => 8.0,
+            "2 2 *" => 5.0,
+        }
+        .unwrap_err();
+
+        assert_eq!(
+            failures
+                .lines()
+                .filter(|line| line.starts_with("golden test"))
+                .count(),
+            2
+        );
+        assert!(failures.contains("\"1 1 +\""));
+        assert!(failures.contains("\"2 2 *\""));
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::PolishNotationToken;
+
+/// Hashes the tokens while ignoring their positions and any spaces, so
+/// `"3 5 +"` and `"35+"` hash the same.
+pub fn expression_hash(tokens: &[(usize, PolishNotationToken)]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    tokens
+        .iter()
+        .filter(|(_, token)| *token != PolishNotationToken::Space)
+        .for_each(|(_, token)| token.hash(&mut hasher));
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tokenize, TokenizerMode};
+
+    fn hash(input: &str) -> u64 {
+        expression_hash(&tokenize(input, TokenizerMode::Standard).unwrap())
+    }
+
+    #[test]
+    fn ignores_whitespace() {
+        assert_eq!(hash("3 5 +"), hash("35+"));
+        assert_eq!(hash("3 5 +"), hash("  3 5  + "));
+    }
+
+    #[test]
+    fn ignores_positions() {
+        let shifted: Vec<_> = tokenize("3 5 +", TokenizerMode::Standard)
+            .unwrap()
+            .into_iter()
+            .map(|(pos, token)| (pos + 10, token))
+            .collect();
+
+        assert_eq!(expression_hash(&shifted), hash("3 5 +"));
+    }
+
+    #[test]
+    fn operator_aliases_hash_the_same() {
+        assert_eq!(hash("3 5 *"), hash("3 5 x"));
+        assert_eq!(hash("3 5 *"), hash("3 5 X"));
+    }
+
+    #[test]
+    fn distinguishes_operators() {
+        assert_ne!(hash("3 5 +"), hash("3 5 -"));
+    }
+
+    #[test]
+    fn distinguishes_operand_order() {
+        assert_ne!(hash("3 5 +"), hash("5 3 +"));
+    }
+
+    #[test]
+    fn empty_and_blank_inputs_hash_the_same() {
+        assert_eq!(hash(""), hash("   "));
+    }
+}
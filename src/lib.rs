@@ -0,0 +1,962 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Display;
+
+#[cfg(feature = "tracing")]
+macro_rules! trace {
+    ($($arg:tt)*) => { tracing::trace!($($arg)*) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace {
+    ($($arg:tt)*) => {};
+}
+
+mod binary;
+mod count;
+#[cfg(feature = "miette")]
+mod diagnostic;
+mod diff;
+mod expression;
+mod fixed;
+mod format;
+#[cfg(fuzzing)]
+mod fuzz;
+mod golden;
+mod hash;
+mod minimize;
+mod profile;
+#[cfg(feature = "rand")]
+mod random;
+mod rational;
+mod source_map;
+mod stack;
+mod stream;
+#[cfg(test)]
+mod test_util;
+mod tokenizer;
+mod transform;
+
+pub use binary::{deserialize_tokens_from_binary, serialize_tokens_to_binary};
+pub use count::{token_count_by_type, TokenKind};
+pub use diff::{token_diff, TokenDiff};
+pub use expression::{evaluate_expression, Expression};
+pub use fixed::evaluate_fixed;
+pub use format::{format_stack, FormatOptions};
+#[cfg(fuzzing)]
+pub use fuzz::fuzz_evaluate;
+pub use golden::golden_test;
+pub use hash::expression_hash;
+pub use minimize::minimize_expression;
+pub use profile::{expression_profile, ExpressionProfile};
+#[cfg(feature = "rand")]
+pub use random::random_expression;
+pub use rational::{evaluate_rational, Rational};
+pub use source_map::{tokenize_with_source_map, SourceMap};
+pub use stack::Stack;
+pub use stream::tokenize_streaming;
+pub use tokenizer::{Tokenizer, TokenizerMode};
+pub use transform::{tokens_apply_map, tokens_filter};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum OperationType {
+    Addition,
+    Subtraction,
+    Multiplication,
+    Division,
+}
+
+impl Display for OperationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OperationType::Addition => write!(f, "+"),
+            OperationType::Subtraction => write!(f, "-"),
+            OperationType::Multiplication => write!(f, "*"),
+            OperationType::Division => write!(f, "/"),
+        }
+    }
+}
+
+impl TryFrom<&str> for OperationType {
+    type Error = TokenError;
+
+    fn try_from(op: &str) -> Result<Self, Self::Error> {
+        match op {
+            "+" => Ok(OperationType::Addition),
+            "-" => Ok(OperationType::Subtraction),
+            "*" | "x" | "X" => Ok(OperationType::Multiplication),
+            "/" => Ok(OperationType::Division),
+            _ => Err(TokenError::UnknownOperator(op.to_string())),
+        }
+    }
+}
+
+impl TryFrom<char> for OperationType {
+    type Error = TokenError;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        OperationType::try_from(&*ch.encode_utf8(&mut [0; 4]))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum PolishNotationToken {
+    Operation(OperationType),
+    Number(u32),
+    Space,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TokenError {
+    InvalidCharacter(usize, char),
+    InvalidBinaryData(usize),
+    ReadError(usize, std::io::ErrorKind),
+    MultipleErrors(Vec<TokenError>),
+    UnknownOperator(String),
+    InvalidNumber(u32),
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::InvalidCharacter(index, ch) => {
+                write!(f, "Invalid character at position {}, \"{}\"", index + 1, ch)
+            }
+            TokenError::InvalidBinaryData(offset) => {
+                write!(f, "Invalid binary token data at byte offset {}", offset)
+            }
+            TokenError::ReadError(index, kind) => write!(
+                f,
+                "Could not read the input at position {}, {}",
+                index + 1,
+                kind
+            ),
+            TokenError::MultipleErrors(errors) => {
+                let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+                write!(f, "{} errors found. {}", errors.len(), messages.join("; "))
+            }
+            TokenError::UnknownOperator(op) => write!(f, "Unknown operator \"{}\"", op),
+            TokenError::InvalidNumber(num) => {
+                write!(f, "The number {} is not a single digit", num)
+            }
+        }
+    }
+}
+
+impl Error for TokenError {}
+
+impl TokenError {
+    /// How serious the error is, higher meaning more severe. A batch of
+    /// errors is as severe as the worst error in it.
+    pub fn severity(&self) -> u8 {
+        match self {
+            TokenError::ReadError(..) => 4,
+            TokenError::InvalidBinaryData(_) => 3,
+            TokenError::InvalidCharacter(..)
+            | TokenError::UnknownOperator(_)
+            | TokenError::InvalidNumber(_) => 2,
+            TokenError::MultipleErrors(errors) => {
+                errors.iter().map(TokenError::severity).max().unwrap_or(0)
+            }
+        }
+    }
+
+    /// The zero-based offset into the input the error was found at. A batch
+    /// of errors reports the first position among its errors, which is the
+    /// most severe one once they are sorted.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            TokenError::InvalidCharacter(index, _) => Some(*index),
+            TokenError::InvalidBinaryData(offset) => Some(*offset),
+            TokenError::ReadError(index, _) => Some(*index),
+            TokenError::MultipleErrors(errors) => errors.iter().find_map(TokenError::position),
+            TokenError::UnknownOperator(_) | TokenError::InvalidNumber(_) => None,
+        }
+    }
+}
+
+impl PartialOrd for TokenError {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        severity_ordering(self.severity(), other.severity(), self == other)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CalculationError {
+    NoNumberFoundForOperation(usize, OperationType),
+    NoResultAvailable(&'static str),
+    IncompleteExpression(usize),
+    DivisionByZero(usize),
+    Overflow(usize, OperationType),
+}
+
+impl Display for CalculationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalculationError::NoNumberFoundForOperation(pos, op) => write!(
+                f,
+                "No number found before the operation {} at position {}",
+                op,
+                pos + 1
+            ),
+            CalculationError::NoResultAvailable(error_msg) => write!(f, "{}", error_msg),
+            CalculationError::IncompleteExpression(stack_size) => write!(
+                f,
+                "Incomplete expression. {} tokens unprocessed.",
+                stack_size - 1
+            ),
+            CalculationError::DivisionByZero(pos) => {
+                write!(f, "Division by zero at position {}", pos + 1)
+            }
+            CalculationError::Overflow(pos, op) => write!(
+                f,
+                "The result of the operation {} at position {} is out of range",
+                op,
+                pos + 1
+            ),
+        }
+    }
+}
+
+impl Error for CalculationError {}
+
+impl CalculationError {
+    /// How serious the error is, higher meaning more severe.
+    pub fn severity(&self) -> u8 {
+        match self {
+            CalculationError::DivisionByZero(_) | CalculationError::Overflow(..) => 4,
+            CalculationError::NoNumberFoundForOperation(..) => 3,
+            CalculationError::IncompleteExpression(_) => 2,
+            CalculationError::NoResultAvailable(_) => 1,
+        }
+    }
+
+    /// The zero-based position of the operation that failed, if the error
+    /// comes from one.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            CalculationError::NoNumberFoundForOperation(pos, _)
+            | CalculationError::DivisionByZero(pos)
+            | CalculationError::Overflow(pos, _) => Some(*pos),
+            // IncompleteExpression holds the stack size, not a position.
+            CalculationError::NoResultAvailable(_) | CalculationError::IncompleteExpression(_) => {
+                None
+            }
+        }
+    }
+}
+
+impl PartialOrd for CalculationError {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        severity_ordering(self.severity(), other.severity(), self == other)
+    }
+}
+
+// Distinct errors of the same severity are left unordered so that the ordering
+// stays consistent with PartialEq.
+fn severity_ordering(lhs: u8, rhs: u8, equal: bool) -> Option<Ordering> {
+    match lhs.cmp(&rhs) {
+        Ordering::Equal if !equal => None,
+        ordering => Some(ordering),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RpdError {
+    Token(TokenError),
+    Calculation(CalculationError),
+}
+
+impl Display for RpdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpdError::Token(err) => write!(f, "{}", err),
+            RpdError::Calculation(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for RpdError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RpdError::Token(err) => Some(err),
+            RpdError::Calculation(err) => Some(err),
+        }
+    }
+}
+
+impl From<TokenError> for RpdError {
+    fn from(err: TokenError) -> Self {
+        RpdError::Token(err)
+    }
+}
+
+impl From<CalculationError> for RpdError {
+    fn from(err: CalculationError) -> Self {
+        RpdError::Calculation(err)
+    }
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip(input), fields(expression = input))
+)]
+pub fn tokenize(
+    input: &str,
+    mode: TokenizerMode,
+) -> Result<Vec<(usize, PolishNotationToken)>, TokenError> {
+    let mut tokenizer = Tokenizer::with_mode(mode);
+    let mut tokens = Vec::new();
+
+    for ch in input.chars() {
+        if let Some(token) = tokenizer.feed(ch) {
+            tokens.push(token?);
+        }
+    }
+
+    if let Some(token) = tokenizer.flush() {
+        tokens.push(token?);
+    }
+
+    Ok(tokens)
+}
+
+/// Tokenizes the whole input instead of stopping at the first invalid
+/// character, and reports every failure together as
+/// [`TokenError::MultipleErrors`].
+pub fn tokenize_collecting_errors(
+    input: &str,
+    mode: TokenizerMode,
+) -> Result<Vec<(usize, PolishNotationToken)>, TokenError> {
+    let mut tokenizer = Tokenizer::with_mode(mode);
+    let mut tokens = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut record = |result| match result {
+        Ok(token) => tokens.push(token),
+        Err(err) => errors.push(err),
+    };
+
+    for ch in input.chars() {
+        if let Some(result) = tokenizer.feed(ch) {
+            record(result);
+        }
+    }
+
+    if let Some(result) = tokenizer.flush() {
+        record(result);
+    }
+
+    if errors.is_empty() {
+        Ok(tokens)
+    } else {
+        // Most severe first; the sort is stable so equally severe errors keep
+        // their order in the input.
+        errors.sort_by_key(|err| std::cmp::Reverse(err.severity()));
+        Err(TokenError::MultipleErrors(errors))
+    }
+}
+
+/// Tokenizes and evaluates `input` in one step.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip(input), fields(expression = input))
+)]
+pub fn evaluate(input: &str) -> Result<u32, RpdError> {
+    let tokens = tokenize(input, TokenizerMode::Standard)?;
+    let hint = max_stack_depth(&tokens);
+
+    Ok(evaluate_with_capacity_hint(tokens, hint)?)
+}
+
+/// Evaluates tokens as they are produced, so a lazy tokenizer can be passed
+/// in without collecting it first.
+///
+/// The stack grows as needed. Callers that already hold the whole token list
+/// can avoid reallocations with [`evaluate_with_capacity_hint`] and
+/// [`max_stack_depth`].
+pub fn calculate_rpd(
+    tokens: impl IntoIterator<Item = (usize, PolishNotationToken)>,
+) -> Result<u32, CalculationError> {
+    evaluate_with_capacity_hint(tokens, 0)
+}
+
+/// Evaluates tokens on a stack that starts with room for `hint` values.
+///
+/// The tokens do not carry the text they were read from, so the span opened
+/// here under the `tracing` feature has no `expression` field. Callers that
+/// hold the source should wrap the call in their own span with
+/// `expression = input`, the way [`evaluate`] does.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip(tokens))
+)]
+pub fn evaluate_with_capacity_hint(
+    tokens: impl IntoIterator<Item = (usize, PolishNotationToken)>,
+    hint: usize,
+) -> Result<u32, CalculationError> {
+    let mut stack = Stack::<u32>::with_capacity(hint);
+
+    for token in tokens {
+        match token.1 {
+            PolishNotationToken::Operation(op) => {
+                trace!(pos = token.0, op = %op, depth = stack.len(), "applying operation");
+                op.apply(token.0, &mut stack)?
+            }
+            PolishNotationToken::Number(num) => {
+                trace!(pos = token.0, num, "pushing number");
+                stack.push(num)
+            }
+            PolishNotationToken::Space => continue,
+        }
+    }
+
+    if stack.len() > 1 {
+        return Err(CalculationError::IncompleteExpression(stack.len()));
+    }
+
+    stack.pop().ok_or(CalculationError::NoResultAvailable(
+        "No result can be generated.",
+    ))
+}
+
+/// Walks the tokens and returns the deepest the stack gets while evaluating
+/// them, without computing any values.
+pub fn max_stack_depth(tokens: &[(usize, PolishNotationToken)]) -> usize {
+    let mut depth: usize = 0;
+    let mut max_depth = 0;
+
+    for (_, token) in tokens {
+        match token {
+            PolishNotationToken::Operation(_) => depth = depth.saturating_sub(2) + 1,
+            PolishNotationToken::Number(_) => depth += 1,
+            PolishNotationToken::Space => continue,
+        }
+
+        max_depth = max_depth.max(depth);
+    }
+
+    max_depth
+}
+
+pub trait Operation {
+    fn apply(&self, op_pos: usize, stack: &mut Stack<u32>) -> Result<(), CalculationError>;
+}
+
+impl Operation for OperationType {
+    fn apply(&self, op_pos: usize, stack: &mut Stack<u32>) -> Result<(), CalculationError> {
+        let y = stack
+            .pop()
+            .ok_or(CalculationError::NoNumberFoundForOperation(op_pos, *self))?;
+        let x = stack
+            .pop()
+            .ok_or(CalculationError::NoNumberFoundForOperation(op_pos, *self))?;
+
+        stack.push(self.compute(op_pos, x, y)?);
+        Ok(())
+    }
+}
+
+impl OperationType {
+    /// Whether `x y op` and `y x op` always give the same result.
+    pub fn is_commutative(&self) -> bool {
+        matches!(
+            self,
+            OperationType::Addition | OperationType::Multiplication
+        )
+    }
+
+    /// Whether `x y op z op` and `x y z op op` always give the same result.
+    pub fn is_associative(&self) -> bool {
+        matches!(
+            self,
+            OperationType::Addition | OperationType::Multiplication
+        )
+    }
+
+    /// The value `e` for which `x e op` and `e x op` both give `x`.
+    pub fn identity_element(&self) -> Option<u32> {
+        match self {
+            OperationType::Addition => Some(0),
+            OperationType::Multiplication => Some(1),
+            OperationType::Subtraction | OperationType::Division => None,
+        }
+    }
+
+    /// The value `z` for which `x z op` and `z x op` both give `z`.
+    pub fn absorbing_element(&self) -> Option<u32> {
+        match self {
+            OperationType::Multiplication => Some(0),
+            OperationType::Addition | OperationType::Subtraction | OperationType::Division => None,
+        }
+    }
+
+    pub(crate) fn compute(&self, op_pos: usize, x: u32, y: u32) -> Result<u32, CalculationError> {
+        match self {
+            OperationType::Addition => x.checked_add(y),
+            OperationType::Subtraction => x.checked_sub(y),
+            OperationType::Multiplication => x.checked_mul(y),
+            OperationType::Division if y == 0 => {
+                return Err(CalculationError::DivisionByZero(op_pos))
+            }
+            OperationType::Division => x.checked_div(y),
+        }
+        .ok_or(CalculationError::Overflow(op_pos, *self))
+    }
+}
+
+/// How tightly `op` binds when the expression is written in infix form. An
+/// operand needs parentheses when its operation binds less tightly than the
+/// one it is nested in.
+pub fn operator_precedence(op: OperationType) -> u8 {
+    match op {
+        OperationType::Addition | OperationType::Subtraction => 1,
+        OperationType::Multiplication | OperationType::Division => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<(usize, PolishNotationToken)> {
+        tokenize(input, TokenizerMode::Standard).unwrap()
+    }
+
+    #[test]
+    fn tokenize_numbers_tokens_by_position() {
+        assert_eq!(
+            tokenize("3 5 +", TokenizerMode::Standard),
+            Ok(vec![
+                (0, PolishNotationToken::Number(3)),
+                (1, PolishNotationToken::Space),
+                (2, PolishNotationToken::Number(5)),
+                (3, PolishNotationToken::Space),
+                (4, PolishNotationToken::Operation(OperationType::Addition)),
+            ])
+        );
+        assert_eq!(tokenize("", TokenizerMode::Standard), Ok(vec![]));
+    }
+
+    #[test]
+    fn tokenize_stops_at_first_invalid_character() {
+        assert_eq!(
+            tokenize("3 a b", TokenizerMode::Standard),
+            Err(TokenError::InvalidCharacter(2, 'a'))
+        );
+        assert_eq!(
+            tokenize("3\t5+", TokenizerMode::Standard),
+            Err(TokenError::InvalidCharacter(1, '\t'))
+        );
+        assert_eq!(
+            tokenize("3 5+\n", TokenizerMode::Standard),
+            Err(TokenError::InvalidCharacter(4, '\n'))
+        );
+    }
+
+    #[test]
+    fn calculate_rpd_reports_arithmetic_errors() {
+        assert_eq!(
+            calculate_rpd(tokenize("3 0 /", TokenizerMode::Standard).unwrap()),
+            Err(CalculationError::DivisionByZero(4))
+        );
+        assert_eq!(
+            calculate_rpd(tokenize("3 5 -", TokenizerMode::Standard).unwrap()),
+            Err(CalculationError::Overflow(4, OperationType::Subtraction))
+        );
+        assert_eq!(
+            calculate_rpd(
+                tokenize(
+                    "9 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 * 9 *",
+                    TokenizerMode::Standard
+                )
+                .unwrap()
+            ),
+            Err(CalculationError::Overflow(
+                40,
+                OperationType::Multiplication
+            ))
+        );
+    }
+
+    #[test]
+    fn arithmetic_error_messages() {
+        assert_eq!(
+            CalculationError::DivisionByZero(4).to_string(),
+            "Division by zero at position 5"
+        );
+        assert_eq!(
+            CalculationError::Overflow(4, OperationType::Subtraction).to_string(),
+            "The result of the operation - at position 5 is out of range"
+        );
+    }
+
+    fn stack_of(values: &[u32]) -> Stack<u32> {
+        values.iter().copied().collect()
+    }
+
+    #[test]
+    fn operation_apply_computes_each_operation() {
+        for (op, expected) in [
+            (OperationType::Addition, 10),
+            (OperationType::Subtraction, 6),
+            (OperationType::Multiplication, 16),
+            (OperationType::Division, 4),
+        ] {
+            let mut stack = stack_of(&[1, 8, 2]);
+            assert_eq!(op.apply(0, &mut stack), Ok(()));
+            assert_eq!(stack, stack_of(&[1, expected]));
+        }
+    }
+
+    #[test]
+    fn operation_apply_needs_two_operands() {
+        let mut stack = stack_of(&[4]);
+        assert_eq!(
+            OperationType::Multiplication.apply(7, &mut stack),
+            Err(CalculationError::NoNumberFoundForOperation(
+                7,
+                OperationType::Multiplication
+            ))
+        );
+
+        let mut stack = Stack::new();
+        assert_eq!(
+            OperationType::Addition.apply(0, &mut stack),
+            Err(CalculationError::NoNumberFoundForOperation(
+                0,
+                OperationType::Addition
+            ))
+        );
+    }
+
+    #[test]
+    fn operation_apply_reports_out_of_range_results() {
+        let mut stack = stack_of(&[u32::MAX, 1]);
+        assert_eq!(
+            OperationType::Addition.apply(2, &mut stack),
+            Err(CalculationError::Overflow(2, OperationType::Addition))
+        );
+
+        let mut stack = stack_of(&[u32::MAX, 2]);
+        assert_eq!(
+            OperationType::Multiplication.apply(2, &mut stack),
+            Err(CalculationError::Overflow(2, OperationType::Multiplication))
+        );
+
+        let mut stack = stack_of(&[1, 2]);
+        assert_eq!(
+            OperationType::Subtraction.apply(2, &mut stack),
+            Err(CalculationError::Overflow(2, OperationType::Subtraction))
+        );
+    }
+
+    #[test]
+    fn operation_apply_rejects_division_by_zero() {
+        let mut stack = stack_of(&[5, 0]);
+        assert_eq!(
+            OperationType::Division.apply(3, &mut stack),
+            Err(CalculationError::DivisionByZero(3))
+        );
+    }
+
+    #[test]
+    fn evaluate_with_capacity_hint_matches_calculate_rpd() {
+        for input in ["3 5 +", "1 2 3 + +", "3 +", ""] {
+            for hint in [0, 1, 16] {
+                assert_eq!(
+                    evaluate_with_capacity_hint(tokens(input), hint),
+                    calculate_rpd(tokens(input))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn max_stack_depth_tracks_deepest_point() {
+        assert_eq!(max_stack_depth(&tokens("")), 0);
+        assert_eq!(max_stack_depth(&tokens("3 5 +")), 2);
+        assert_eq!(max_stack_depth(&tokens("1 2 3 4 + + +")), 4);
+        assert_eq!(max_stack_depth(&tokens("1 2 + 3 + 4 +")), 2);
+        assert_eq!(max_stack_depth(&tokens("+ +")), 1);
+    }
+
+    #[test]
+    fn calculate_rpd_accepts_lazy_tokens() {
+        let mut tokenizer = Tokenizer::new();
+        let lazy = "3 5 + 2 *"
+            .chars()
+            .filter_map(|ch| tokenizer.feed(ch))
+            .map(Result::unwrap);
+
+        assert_eq!(calculate_rpd(lazy), Ok(16));
+    }
+
+    #[test]
+    fn tokenize_collecting_errors_reports_every_invalid_character() {
+        assert_eq!(
+            tokenize_collecting_errors("3 5 +", TokenizerMode::Standard),
+            Ok(tokens("3 5 +"))
+        );
+        assert_eq!(
+            tokenize_collecting_errors("3a5b", TokenizerMode::Standard),
+            Err(TokenError::MultipleErrors(vec![
+                TokenError::InvalidCharacter(1, 'a'),
+                TokenError::InvalidCharacter(3, 'b'),
+            ]))
+        );
+        assert_eq!(
+            tokenize_collecting_errors("é a", TokenizerMode::Standard),
+            Err(TokenError::MultipleErrors(vec![
+                TokenError::InvalidCharacter(0, 'é'),
+                TokenError::InvalidCharacter(3, 'a'),
+            ]))
+        );
+    }
+
+    #[test]
+    fn operation_type_parses_from_char_and_str() {
+        assert_eq!(OperationType::try_from('+'), Ok(OperationType::Addition));
+        assert_eq!(
+            OperationType::try_from('X'),
+            Ok(OperationType::Multiplication)
+        );
+        assert_eq!(OperationType::try_from("-"), Ok(OperationType::Subtraction));
+        assert_eq!(OperationType::try_from("/"), Ok(OperationType::Division));
+        assert_eq!(
+            OperationType::try_from("++"),
+            Err(TokenError::UnknownOperator(String::from("++")))
+        );
+        assert_eq!(
+            OperationType::try_from('%'),
+            Err(TokenError::UnknownOperator(String::from("%")))
+        );
+    }
+
+    #[test]
+    fn errors_are_ordered_by_severity() {
+        assert!(CalculationError::DivisionByZero(0) > CalculationError::IncompleteExpression(2));
+        assert!(
+            CalculationError::IncompleteExpression(2)
+                > CalculationError::NoResultAvailable("No result can be generated.")
+        );
+        assert!(
+            TokenError::ReadError(0, std::io::ErrorKind::Other)
+                > TokenError::InvalidCharacter(0, 'a')
+        );
+        assert_eq!(
+            TokenError::InvalidCharacter(0, 'a').partial_cmp(&TokenError::InvalidCharacter(1, 'b')),
+            None
+        );
+        assert_eq!(
+            TokenError::MultipleErrors(vec![
+                TokenError::InvalidCharacter(0, 'a'),
+                TokenError::InvalidBinaryData(0),
+            ])
+            .severity(),
+            TokenError::InvalidBinaryData(0).severity()
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn evaluate_traces_the_expression() {
+        let (spans, events) = crate::test_util::record_spans(|| {
+            let _ = evaluate("3 5 +");
+        });
+
+        assert!(spans.contains(&("evaluate", Some(String::from("3 5 +")))));
+        assert!(spans.contains(&("evaluate_with_capacity_hint", None)));
+        assert!(events > 0);
+    }
+
+    #[test]
+    fn evaluate_wraps_both_error_kinds() {
+        assert_eq!(evaluate("3 5 +"), Ok(8));
+        assert_eq!(evaluate("9 3 / 2 *"), Ok(6));
+        assert_eq!(
+            evaluate("3 a"),
+            Err(RpdError::Token(TokenError::InvalidCharacter(2, 'a')))
+        );
+        assert_eq!(
+            evaluate("3 0 /"),
+            Err(RpdError::Calculation(CalculationError::DivisionByZero(4)))
+        );
+    }
+
+    #[test]
+    fn evaluate_returns_errors_for_malformed_input() {
+        assert!(evaluate("1 2 -").is_err());
+        assert!(evaluate("+").is_err());
+        assert!(evaluate("").is_err());
+        assert!(evaluate("3 é +").is_err());
+    }
+
+    #[test]
+    fn calculate_rpd_evaluates_multi_token_expressions() {
+        assert_eq!(calculate_rpd(tokens("3 5 +")), Ok(8));
+        assert_eq!(calculate_rpd(tokens("3 5 + 2 *")), Ok(16));
+        assert_eq!(calculate_rpd(tokens("9 3 / 4 x")), Ok(12));
+        assert_eq!(calculate_rpd(tokens("98*3-")), Ok(69));
+        assert_eq!(calculate_rpd(tokens("7")), Ok(7));
+    }
+
+    #[test]
+    fn calculate_rpd_reports_missing_operands() {
+        assert_eq!(
+            calculate_rpd(tokens("3 +")),
+            Err(CalculationError::NoNumberFoundForOperation(
+                2,
+                OperationType::Addition
+            ))
+        );
+        assert_eq!(
+            calculate_rpd(tokens("/")),
+            Err(CalculationError::NoNumberFoundForOperation(
+                0,
+                OperationType::Division
+            ))
+        );
+    }
+
+    #[test]
+    fn calculate_rpd_reports_empty_and_incomplete_expressions() {
+        assert_eq!(
+            calculate_rpd(tokens("")),
+            Err(CalculationError::NoResultAvailable(
+                "No result can be generated."
+            ))
+        );
+        assert_eq!(
+            calculate_rpd(tokens("   ")),
+            Err(CalculationError::NoResultAvailable(
+                "No result can be generated."
+            ))
+        );
+        assert_eq!(
+            calculate_rpd(tokens("1 2 3 +")),
+            Err(CalculationError::IncompleteExpression(2))
+        );
+    }
+
+    #[test]
+    fn operation_type_displays_canonical_symbol() {
+        assert_eq!(OperationType::Addition.to_string(), "+");
+        assert_eq!(OperationType::Subtraction.to_string(), "-");
+        assert_eq!(OperationType::Multiplication.to_string(), "*");
+        assert_eq!(OperationType::Division.to_string(), "/");
+    }
+
+    #[test]
+    fn error_messages_use_one_based_positions() {
+        assert_eq!(
+            TokenError::InvalidCharacter(0, 'a').to_string(),
+            "Invalid character at position 1, \"a\""
+        );
+        assert_eq!(
+            CalculationError::IncompleteExpression(3).to_string(),
+            "Incomplete expression. 2 tokens unprocessed."
+        );
+    }
+
+    #[test]
+    fn commutative_and_associative_operations() {
+        assert!(OperationType::Addition.is_commutative());
+        assert!(OperationType::Multiplication.is_commutative());
+        assert!(!OperationType::Subtraction.is_commutative());
+        assert!(!OperationType::Division.is_commutative());
+
+        assert!(OperationType::Addition.is_associative());
+        assert!(OperationType::Multiplication.is_associative());
+        assert!(!OperationType::Subtraction.is_associative());
+        assert!(!OperationType::Division.is_associative());
+    }
+
+    #[test]
+    fn identity_elements() {
+        assert_eq!(OperationType::Addition.identity_element(), Some(0));
+        assert_eq!(OperationType::Multiplication.identity_element(), Some(1));
+        assert_eq!(OperationType::Subtraction.identity_element(), None);
+        assert_eq!(OperationType::Division.identity_element(), None);
+
+        for op in [OperationType::Addition, OperationType::Multiplication] {
+            let identity = op.identity_element().unwrap();
+            assert_eq!(op.compute(0, 7, identity), Ok(7));
+            assert_eq!(op.compute(0, identity, 7), Ok(7));
+        }
+    }
+
+    #[test]
+    fn absorbing_elements() {
+        assert_eq!(OperationType::Multiplication.absorbing_element(), Some(0));
+        assert_eq!(OperationType::Addition.absorbing_element(), None);
+        assert_eq!(OperationType::Subtraction.absorbing_element(), None);
+        assert_eq!(OperationType::Division.absorbing_element(), None);
+
+        let zero = OperationType::Multiplication.absorbing_element().unwrap();
+        assert_eq!(OperationType::Multiplication.compute(0, 7, zero), Ok(0));
+        assert_eq!(OperationType::Multiplication.compute(0, zero, 7), Ok(0));
+    }
+
+    #[test]
+    fn calculation_error_positions() {
+        assert_eq!(
+            calculate_rpd(tokens("3 +")).unwrap_err().position(),
+            Some(2)
+        );
+        assert_eq!(
+            calculate_rpd(tokens("3 0 /")).unwrap_err().position(),
+            Some(4)
+        );
+        assert_eq!(
+            calculate_rpd(tokens("3 5 -")).unwrap_err().position(),
+            Some(4)
+        );
+        assert_eq!(
+            calculate_rpd(tokens("1 2 3 +")).unwrap_err().position(),
+            None
+        );
+        assert_eq!(calculate_rpd(tokens("")).unwrap_err().position(), None);
+    }
+
+    #[test]
+    fn token_error_positions() {
+        assert_eq!(
+            tokenize("3 5 ?", TokenizerMode::Standard)
+                .unwrap_err()
+                .position(),
+            Some(4)
+        );
+        assert_eq!(TokenError::InvalidBinaryData(7).position(), Some(7));
+        assert_eq!(
+            TokenError::ReadError(3, std::io::ErrorKind::Other).position(),
+            Some(3)
+        );
+        assert_eq!(
+            TokenError::UnknownOperator(String::from("%")).position(),
+            None
+        );
+        assert_eq!(TokenError::InvalidNumber(12).position(), None);
+    }
+
+    #[test]
+    fn multiple_errors_report_the_first_position() {
+        let err = tokenize_collecting_errors("3 a b", TokenizerMode::Standard).unwrap_err();
+        assert_eq!(err.position(), Some(2));
+
+        let err = TokenError::MultipleErrors(vec![
+            TokenError::UnknownOperator(String::from("%")),
+            TokenError::InvalidCharacter(5, 'a'),
+        ]);
+        assert_eq!(err.position(), Some(5));
+        assert_eq!(TokenError::MultipleErrors(vec![]).position(), None);
+    }
+
+    #[test]
+    fn operator_precedences() {
+        assert_eq!(operator_precedence(OperationType::Addition), 1);
+        assert_eq!(operator_precedence(OperationType::Subtraction), 1);
+        assert_eq!(operator_precedence(OperationType::Multiplication), 2);
+        assert_eq!(operator_precedence(OperationType::Division), 2);
+    }
+}
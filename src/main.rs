@@ -1,79 +1,225 @@
-use std::collections::VecDeque;
-use std::error::Error;
-use std::fmt::Display;
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::BuildHasher;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum OperationType {
-    Addition,
-    Subtraction,
-    Multiplication,
-    Division,
+use rpd::{
+    evaluate_rational, evaluate_with_capacity_hint, max_stack_depth, token_count_by_type, tokenize,
+    tokenize_collecting_errors, Rational, TokenError, TokenKind, TokenizerMode,
+};
+
+struct Options {
+    echo: bool,
+    echo_format: String,
+    time: bool,
+    ops_count: bool,
+    collect_errors: bool,
+    strict: bool,
+    rational: bool,
 }
 
-impl Display for OperationType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            OperationType::Addition => write!(f, "+"),
-            OperationType::Subtraction => write!(f, "-"),
-            OperationType::Multiplication => write!(f, "*"),
-            OperationType::Division => write!(f, "/"),
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            echo: false,
+            echo_format: String::from("{expr} = {result}"),
+            time: false,
+            ops_count: false,
+            collect_errors: false,
+            strict: false,
+            rational: false,
         }
     }
 }
 
-enum PolishNotationToken {
-    Operation(OperationType),
-    Number(u32),
-    Space,
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--echo" => options.echo = true,
+            "--echo-format" => {
+                options.echo_format = args
+                    .next()
+                    .ok_or_else(|| String::from("Missing value for --echo-format"))?;
+                options.echo = true;
+            }
+            "--time" => options.time = true,
+            "--ops-count" => options.ops_count = true,
+            "--collect-errors" => options.collect_errors = true,
+            "--strict" => options.strict = true,
+            "--rational" => options.rational = true,
+            _ => return Err(format!("Unknown argument \"{}\"", arg)),
+        }
+    }
+
+    Ok(options)
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum TokenError {
-    InvalidCharacter(usize, char),
+fn run_command(
+    command: &str,
+    options: &mut Options,
+    history: &mut Vec<String>,
+) -> Result<(), String> {
+    let (name, arg) = command
+        .split_once(' ')
+        .map_or((command, ""), |(name, arg)| (name, arg.trim()));
+
+    match (name, arg) {
+        ("echo", "on") => options.echo = true,
+        ("echo", "off") => options.echo = false,
+        ("time", "on") => options.time = true,
+        ("time", "off") => options.time = false,
+        ("rational", "on") => options.rational = true,
+        ("rational", "off") => options.rational = false,
+        ("load", path) if !path.is_empty() => load_script(path, options, history)?,
+        ("save", path) if !path.is_empty() => save_history(path, history)?,
+        ("edit", initial) => edit_expression(initial, options, history)?,
+        _ => return Err(format!("Unknown command \":{}\"", command)),
+    }
+
+    Ok(())
 }
 
-impl Display for TokenError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TokenError::InvalidCharacter(index, ch) => {
-                write!(f, "Invalid character at position {}, \"{}\"", index + 1, ch)
-            }
+fn load_script(path: &str, options: &Options, history: &mut Vec<String>) -> Result<(), String> {
+    let script = fs::read_to_string(path)
+        .map_err(|err| format!("An error occurred while reading \"{}\"!\n{}", path, err))?;
+
+    for line in script.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if evaluate_line(line, options) {
+            history.push(String::from(line));
         }
     }
+
+    Ok(())
 }
 
-impl Error for TokenError {}
+fn save_history(path: &str, history: &[String]) -> Result<(), String> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let mut script = format!("# rpd session saved {}\n", timestamp);
+    for line in history {
+        script.push_str(line);
+        script.push('\n');
+    }
 
-#[derive(Debug, PartialEq, Eq)]
-enum CalculationError {
-    NoNumberFoundForOperation(usize, OperationType),
-    NoResultAvailable(&'static str),
-    IncompleteExpression(usize),
+    fs::write(path, script)
+        .map_err(|err| format!("An error occurred while writing \"{}\"!\n{}", path, err))
 }
 
-impl Display for CalculationError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CalculationError::NoNumberFoundForOperation(pos, op) => write!(
-                f,
-                "No number found before the operation {} at position {}",
-                op,
-                pos + 1
-            ),
-            CalculationError::NoResultAvailable(error_msg) => write!(f, "{}", error_msg),
-            CalculationError::IncompleteExpression(stack_size) => write!(
-                f,
-                "Incomplete expression. {} tokens unprocessed.",
-                stack_size - 1
-            ),
+// The edit file is created exclusively, so an existing file or symlink at the
+// path is never written through. A taken name is retried with a new random
+// suffix.
+fn create_edit_file() -> io::Result<(PathBuf, fs::File)> {
+    let random = RandomState::new();
+    let mut attempt: u32 = 0;
+
+    loop {
+        let path = std::env::temp_dir().join(format!(
+            "rpd-edit-{}-{:016x}.rpd",
+            std::process::id(),
+            random.hash_one(attempt)
+        ));
+
+        let mut open_options = fs::OpenOptions::new();
+        open_options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut open_options, 0o600);
+
+        match open_options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists && attempt < 16 => attempt += 1,
+            Err(err) => return Err(err),
         }
     }
 }
 
-impl Error for CalculationError {}
+fn edit_expression(
+    initial: &str,
+    options: &Options,
+    history: &mut Vec<String>,
+) -> Result<(), String> {
+    let initial = if initial.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", initial)
+    };
+
+    let (path, mut file) = create_edit_file()
+        .map_err(|err| format!("An error occurred while creating the edit file!\n{}", err))?;
+
+    // The handle is closed before the editor starts, since some platforms do
+    // not let two processes hold the file open.
+    let written = file.write_all(initial.as_bytes());
+    drop(file);
+
+    if let Err(err) = written {
+        let _ = fs::remove_file(&path);
+        return Err(format!(
+            "An error occurred while writing the edit file!\n{}",
+            err
+        ));
+    }
+
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| String::from("vi"));
+    let mut editor_parts = editor.split_whitespace();
+    let status = Command::new(editor_parts.next().unwrap_or("vi"))
+        .args(editor_parts)
+        .arg(&path)
+        .status();
+
+    let content = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => return Err(format!("The editor \"{}\" exited with {}", editor, status)),
+        Err(err) => {
+            return Err(format!(
+                "An error occurred while starting the editor \"{}\"!\n{}",
+                editor, err
+            ))
+        }
+    }
+
+    let content = content
+        .map_err(|err| format!("An error occurred while reading the edit file!\n{}", err))?;
+
+    // Lines are joined so that a long expression can be spread over several lines.
+    let expression = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if !expression.is_empty() && evaluate_line(&expression, options) {
+        history.push(expression);
+    }
+
+    Ok(())
+}
 
 fn main() {
+    let mut options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    let mut history = Vec::new();
+
     println!("rpd - Reverse Polish Notation calculator");
     println!("Type \"exit\" to exit");
 
@@ -94,126 +240,400 @@ fn main() {
             std::process::exit(0);
         }
 
-        let token_result: Result<Vec<(usize, PolishNotationToken)>, TokenError> = input
-            .trim()
-            .chars()
-            .enumerate()
-            .map(|(pos, ch)| parse_rpd_token(pos, ch))
-            .collect();
+        if let Some(command) = input.trim().strip_prefix(':') {
+            if let Err(err) = run_command(command, &mut options, &mut history) {
+                eprintln!("{}", err);
+            }
+            continue;
+        }
 
-        match token_result {
-            Ok(tokens) => match calculate_rpd(tokens) {
-                Ok(result) => println!("{}", result),
-                Err(err) => eprintln!(
+        if evaluate_line(input.trim(), &options) {
+            history.push(String::from(input.trim()));
+        }
+    }
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", skip(input, options), fields(expression = input))
+)]
+fn evaluate_line(input: &str, options: &Options) -> bool {
+    let start = Instant::now();
+
+    let mode = if options.strict {
+        TokenizerMode::Strict
+    } else {
+        TokenizerMode::Standard
+    };
+
+    let token_result = if options.collect_errors {
+        tokenize_collecting_errors(input, mode)
+    } else {
+        tokenize(input, mode)
+    };
+
+    // A whole number displays the same as a u32, so the integer result is
+    // carried as a Rational too and both modes print through one path.
+    let calculation_result = token_result.map(|tokens| {
+        let result = if options.rational {
+            evaluate_rational(tokens.iter().copied())
+        } else {
+            let hint = max_stack_depth(&tokens);
+            evaluate_with_capacity_hint(tokens.iter().copied(), hint).map(Rational::from)
+        };
+        (tokens, result)
+    });
+
+    if options.time {
+        eprintln!("[{:.2} µs]", start.elapsed().as_secs_f64() * 1e6);
+    }
+
+    // Counted after the time is taken, so --time only covers tokenizing and
+    // evaluating. Every operation token is applied exactly once in a
+    // successful evaluation.
+    if options.ops_count {
+        if let Ok((tokens, Ok(_))) = &calculation_result {
+            let ops = token_count_by_type(tokens)
+                .get(&TokenKind::Operation)
+                .copied()
+                .unwrap_or(0);
+            eprintln!("[{} ops]", ops);
+        }
+    }
+
+    match calculation_result {
+        Ok((_, calculation)) => match calculation {
+            Ok(result) if options.echo => {
+                println!(
+                    "{}",
+                    options
+                        .echo_format
+                        .replace("{expr}", input)
+                        .replace("{result}", &result.to_string())
+                );
+                true
+            }
+            Ok(result) => {
+                println!("{}", result);
+                true
+            }
+            Err(err) => {
+                eprintln!(
                     "An error occurred while calculating reversed polish notation. {}",
                     err
-                ),
-            },
-            Err(token_error) => eprintln!(
+                );
+                false
+            }
+        },
+        Err(token_error) => {
+            eprintln!(
                 "An error occurred while evaluating reversed polish notation. {}",
-                token_error
-            ),
+                with_char_positions(token_error, input)
+            );
+            false
         }
     }
 }
 
-fn calculate_rpd(tokens: Vec<(usize, PolishNotationToken)>) -> Result<u32, CalculationError> {
-    let mut stack = VecDeque::<u32>::new();
+// Token errors hold byte offsets into the line, which only match the column
+// the user sees while the line is ASCII, so they are counted in characters
+// before the error is printed.
+fn with_char_positions(err: TokenError, input: &str) -> TokenError {
+    let column = |offset: usize| {
+        input
+            .get(..offset)
+            .map_or(offset, |prefix| prefix.chars().count())
+    };
 
-    for token in tokens {
-        match token.1 {
-            PolishNotationToken::Operation(op) => apply_op(token.0, op, &mut stack)?,
-            PolishNotationToken::Number(num) => stack.push_back(num),
-            PolishNotationToken::Space => continue,
+    match err {
+        TokenError::InvalidCharacter(offset, ch) => {
+            TokenError::InvalidCharacter(column(offset), ch)
         }
+        TokenError::ReadError(offset, kind) => TokenError::ReadError(column(offset), kind),
+        TokenError::MultipleErrors(errors) => TokenError::MultipleErrors(
+            errors
+                .into_iter()
+                .map(|err| with_char_positions(err, input))
+                .collect(),
+        ),
+        err => err,
     }
+}
 
-    if stack.len() > 1 {
-        return Err(CalculationError::IncompleteExpression(stack.len()));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| String::from(*arg))
+            .collect::<Vec<_>>()
+            .into_iter()
     }
 
-    stack
-        .pop_back()
-        .ok_or_else(|| CalculationError::NoResultAvailable("No result can be generated."))
-}
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("rpd-test-{}-{}", std::process::id(), name))
+    }
 
-fn apply_op(
-    op_pos: usize,
-    op_type: OperationType,
-    stack: &mut VecDeque<u32>,
-) -> Result<(), CalculationError> {
-    match op_type {
-        OperationType::Addition => {
-            let y = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-            let x = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-
-            Ok(stack.push_back(x + y))
-        }
-        OperationType::Subtraction => {
-            let y = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-            let x = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-
-            Ok(stack.push_back(x - y))
-        }
-        OperationType::Multiplication => {
-            let y = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-            let x = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-
-            Ok(stack.push_back(x * y))
-        }
-        OperationType::Division => {
-            let y = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-            let x = stack
-                .pop_back()
-                .ok_or_else(|| CalculationError::NoNumberFoundForOperation(op_pos, op_type))?;
-
-            Ok(stack.push_back(x / y))
-        }
+    #[test]
+    fn parse_args_defaults() {
+        let options = parse_args(args(&[])).unwrap();
+
+        assert!(!options.echo);
+        assert_eq!(options.echo_format, "{expr} = {result}");
     }
-}
 
-fn parse_rpd_token(index: usize, ch: char) -> Result<(usize, PolishNotationToken), TokenError> {
-    match ch {
-        '+' => Ok((
-            index,
-            PolishNotationToken::Operation(OperationType::Addition),
-        )),
-        '-' => Ok((
-            index,
-            PolishNotationToken::Operation(OperationType::Subtraction),
-        )),
-        '*' => Ok((
-            index,
-            PolishNotationToken::Operation(OperationType::Multiplication),
-        )),
-        'x' => Ok((
-            index,
-            PolishNotationToken::Operation(OperationType::Multiplication),
-        )),
-        'X' => Ok((
-            index,
-            PolishNotationToken::Operation(OperationType::Multiplication),
-        )),
-        '/' => Ok((
-            index,
-            PolishNotationToken::Operation(OperationType::Division),
-        )),
-        '0'..='9' => Ok((index, PolishNotationToken::Number(ch.to_digit(10).unwrap()))),
-        ' ' => Ok((index, PolishNotationToken::Space)),
-        _ => Err(TokenError::InvalidCharacter(index, ch)),
+    #[test]
+    fn parse_args_echo() {
+        let options = parse_args(args(&["--echo"])).unwrap();
+
+        assert!(options.echo);
+        assert_eq!(options.echo_format, "{expr} = {result}");
+    }
+
+    #[test]
+    fn parse_args_echo_format_enables_echo() {
+        let options = parse_args(args(&["--echo-format", "{result}"])).unwrap();
+
+        assert!(options.echo);
+        assert_eq!(options.echo_format, "{result}");
+        assert_eq!(
+            parse_args(args(&["--echo-format"])).err().unwrap(),
+            "Missing value for --echo-format"
+        );
+    }
+
+    #[test]
+    fn parse_args_rejects_unknown_arguments() {
+        assert_eq!(
+            parse_args(args(&["--verbose"])).err().unwrap(),
+            "Unknown argument \"--verbose\""
+        );
+    }
+
+    #[test]
+    fn run_command_toggles_echo() {
+        let mut options = Options::default();
+        let mut history = Vec::new();
+
+        run_command("echo on", &mut options, &mut history).unwrap();
+        assert!(options.echo);
+
+        run_command("echo off", &mut options, &mut history).unwrap();
+        assert!(!options.echo);
+    }
+
+    #[test]
+    fn run_command_rejects_unknown_commands() {
+        let mut options = Options::default();
+        let mut history = Vec::new();
+
+        assert_eq!(
+            run_command("quit", &mut options, &mut history).unwrap_err(),
+            "Unknown command \":quit\""
+        );
+        assert!(run_command("echo", &mut options, &mut history).is_err());
+        assert!(run_command("echo maybe", &mut options, &mut history).is_err());
+    }
+
+    #[test]
+    fn parse_args_time() {
+        assert!(!parse_args(args(&[])).unwrap().time);
+        assert!(parse_args(args(&["--time"])).unwrap().time);
+    }
+
+    #[test]
+    fn run_command_toggles_time() {
+        let mut options = Options::default();
+        let mut history = Vec::new();
+
+        run_command("time on", &mut options, &mut history).unwrap();
+        assert!(options.time);
+
+        run_command("time off", &mut options, &mut history).unwrap();
+        assert!(!options.time);
+    }
+
+    #[test]
+    fn parse_args_rational() {
+        assert!(!parse_args(args(&[])).unwrap().rational);
+        assert!(parse_args(args(&["--rational"])).unwrap().rational);
+    }
+
+    #[test]
+    fn run_command_toggles_rational() {
+        let mut options = Options::default();
+        let mut history = Vec::new();
+
+        run_command("rational on", &mut options, &mut history).unwrap();
+        assert!(options.rational);
+
+        run_command("rational off", &mut options, &mut history).unwrap();
+        assert!(!options.rational);
+    }
+
+    #[test]
+    fn evaluate_line_keeps_fractions_in_rational_mode() {
+        let mut options = Options::default();
+        assert!(!evaluate_line("1 2 -", &options));
+
+        options.rational = true;
+        assert!(evaluate_line("1 2 -", &options));
+        assert!(evaluate_line("1 3 / 1 3 / +", &options));
+        assert!(!evaluate_line("1 0 /", &options));
+    }
+
+    #[test]
+    fn parse_args_ops_count() {
+        assert!(!parse_args(args(&[])).unwrap().ops_count);
+
+        let options = parse_args(args(&["--time", "--ops-count"])).unwrap();
+        assert!(options.ops_count);
+        assert!(options.time);
+        assert!(!options.echo);
+    }
+
+    #[test]
+    fn parse_args_collect_errors() {
+        assert!(!parse_args(args(&[])).unwrap().collect_errors);
+        assert!(
+            parse_args(args(&["--collect-errors"]))
+                .unwrap()
+                .collect_errors
+        );
+    }
+
+    #[test]
+    fn evaluate_line_collects_errors() {
+        let options = Options {
+            collect_errors: true,
+            ..Options::default()
+        };
+
+        assert!(evaluate_line("3 5 +", &options));
+        assert!(!evaluate_line("3 a b", &options));
+    }
+
+    #[test]
+    fn token_errors_print_character_columns() {
+        let err = tokenize_collecting_errors("é a", TokenizerMode::Standard).unwrap_err();
+
+        assert_eq!(
+            with_char_positions(err, "é a").to_string(),
+            "2 errors found. Invalid character at position 1, \"é\"; \
+             Invalid character at position 3, \"a\""
+        );
+        assert_eq!(
+            with_char_positions(TokenError::InvalidCharacter(5, 'b'), "ab€b").to_string(),
+            "Invalid character at position 4, \"b\""
+        );
+    }
+
+    #[test]
+    fn parse_args_strict() {
+        assert!(!parse_args(args(&[])).unwrap().strict);
+        assert!(parse_args(args(&["--strict"])).unwrap().strict);
+    }
+
+    #[test]
+    fn evaluate_line_rejects_aliases_in_strict_mode() {
+        let mut options = Options::default();
+        assert!(evaluate_line("3 5 x", &options));
+
+        options.strict = true;
+        assert!(evaluate_line("3 5 *", &options));
+        assert!(!evaluate_line("3 5 x", &options));
+
+        options.collect_errors = true;
+        assert!(!evaluate_line("3 5 x", &options));
+    }
+
+    #[test]
+    fn load_script_keeps_successful_lines() {
+        let path = temp_path("load.rpd");
+        fs::write(&path, "# comment\n3 5 +\n\n3 0 /\n  2 2 *  \n").unwrap();
+
+        let mut history = Vec::new();
+        let result = load_script(path.to_str().unwrap(), &Options::default(), &mut history);
+        let _ = fs::remove_file(&path);
+
+        assert!(result.is_ok());
+        assert_eq!(history, vec!["3 5 +", "2 2 *"]);
+    }
+
+    #[test]
+    fn load_script_reports_missing_files() {
+        let path = temp_path("missing.rpd");
+        let mut history = Vec::new();
+
+        assert!(load_script(path.to_str().unwrap(), &Options::default(), &mut history).is_err());
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn run_command_load_and_save_need_a_path() {
+        let mut options = Options::default();
+        let mut history = Vec::new();
+
+        assert!(run_command("load", &mut options, &mut history).is_err());
+        assert!(run_command("load ", &mut options, &mut history).is_err());
+        assert!(run_command("save", &mut options, &mut history).is_err());
+    }
+
+    #[test]
+    fn save_history_writes_header_and_lines() {
+        let path = temp_path("save.rpd");
+        let history = vec![String::from("3 5 +"), String::from("2 2 *")];
+
+        save_history(path.to_str().unwrap(), &history).unwrap();
+        let script = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let mut lines = script.lines();
+        assert!(lines.next().unwrap().starts_with("# rpd session saved "));
+        assert_eq!(lines.collect::<Vec<_>>(), vec!["3 5 +", "2 2 *"]);
+    }
+
+    #[test]
+    fn evaluate_line_reports_success() {
+        let options = Options::default();
+
+        assert!(evaluate_line("3 5 +", &options));
+        assert!(!evaluate_line("3 +", &options));
+        assert!(!evaluate_line("3 a +", &options));
+    }
+
+    // The only test that touches $EDITOR, so setting it cannot race with others.
+    #[cfg(unix)]
+    #[test]
+    fn edit_expression_uses_the_editor_result() {
+        let options = Options::default();
+        let mut history = Vec::new();
+
+        std::env::set_var("EDITOR", "true");
+        assert!(edit_expression("3 5 +", &options, &mut history).is_ok());
+        assert!(edit_expression("", &options, &mut history).is_ok());
+        assert_eq!(history, vec!["3 5 +"]);
+
+        std::env::set_var("EDITOR", "false");
+        assert!(edit_expression("2 2 *", &options, &mut history).is_err());
+        assert_eq!(history, vec!["3 5 +"]);
+    }
+
+    #[test]
+    fn create_edit_file_creates_a_new_private_file() {
+        let (first, _) = create_edit_file().unwrap();
+        let (second, _) = create_edit_file().unwrap();
+
+        assert_ne!(first, second);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_file(first).unwrap();
+        fs::remove_file(second).unwrap();
     }
 }
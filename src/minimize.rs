@@ -0,0 +1,124 @@
+use crate::{calculate_rpd, tokenize, PolishNotationToken, TokenizerMode};
+
+/// Greedily removes runs of tokens from `input` while the expression still
+/// evaluates to within `eps` of `target`.
+///
+/// Removing a single token from a valid expression almost always unbalances
+/// it, so runs of every length are tried, longest first, and the search
+/// restarts after each successful removal.
+///
+/// Returns the simplified expression with tokens separated by single spaces,
+/// or `None` if `input` does not evaluate close to `target` or no token could
+/// be removed.
+pub fn minimize_expression(input: &str, target: f64, eps: f64) -> Option<String> {
+    let mut tokens: Vec<PolishNotationToken> = tokenize(input, TokenizerMode::Standard)
+        .ok()?
+        .into_iter()
+        .map(|(_, token)| token)
+        .filter(|token| *token != PolishNotationToken::Space)
+        .collect();
+
+    if !within_target(&tokens, target, eps) {
+        return None;
+    }
+
+    let mut simplified = false;
+
+    while let Some(candidate) = remove_one_run(&tokens, target, eps) {
+        tokens = candidate;
+        simplified = true;
+    }
+
+    if !simplified {
+        return None;
+    }
+
+    let expression: Vec<String> = tokens
+        .iter()
+        .map(|token| match token {
+            PolishNotationToken::Operation(op) => op.to_string(),
+            PolishNotationToken::Number(num) => num.to_string(),
+            PolishNotationToken::Space => unreachable!("spaces are filtered out"),
+        })
+        .collect();
+
+    Some(expression.join(" "))
+}
+
+fn remove_one_run(
+    tokens: &[PolishNotationToken],
+    target: f64,
+    eps: f64,
+) -> Option<Vec<PolishNotationToken>> {
+    for len in (1..tokens.len()).rev() {
+        for start in 0..=tokens.len() - len {
+            let candidate: Vec<PolishNotationToken> = tokens[..start]
+                .iter()
+                .chain(&tokens[start + len..])
+                .copied()
+                .collect();
+
+            if within_target(&candidate, target, eps) {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+fn within_target(tokens: &[PolishNotationToken], target: f64, eps: f64) -> bool {
+    calculate_rpd(tokens.iter().copied().enumerate())
+        .is_ok_and(|result| (f64::from(result) - target).abs() <= eps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_redundant_tail() {
+        assert_eq!(
+            minimize_expression("3 5 + 0 +", 8.0, 0.0),
+            Some(String::from("3 5 +"))
+        );
+        assert_eq!(
+            minimize_expression("3 5 + 1 *", 8.0, 0.0),
+            Some(String::from("3 5 +"))
+        );
+    }
+
+    #[test]
+    fn reduces_to_a_single_number_when_possible() {
+        assert_eq!(
+            minimize_expression("9 0 + 1 *", 9.0, 0.0),
+            Some(String::from("9"))
+        );
+    }
+
+    #[test]
+    fn uses_tolerance() {
+        assert_eq!(
+            minimize_expression("3 4 + 1 +", 7.0, 1.0),
+            Some(String::from("3 4 +"))
+        );
+        assert_eq!(minimize_expression("3 4 + 1 +", 7.0, 0.0), None);
+    }
+
+    #[test]
+    fn none_when_nothing_can_be_removed() {
+        assert_eq!(minimize_expression("3 5 +", 8.0, 0.0), None);
+        assert_eq!(minimize_expression("8", 8.0, 0.0), None);
+    }
+
+    #[test]
+    fn none_when_input_misses_target() {
+        assert_eq!(minimize_expression("3 5 + 0 +", 7.0, 0.5), None);
+    }
+
+    #[test]
+    fn none_for_invalid_input() {
+        assert_eq!(minimize_expression("3 a +", 3.0, 0.0), None);
+        assert_eq!(minimize_expression("3 0 /", 0.0, 0.0), None);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{max_stack_depth, tokenize, PolishNotationToken, TokenError, TokenizerMode};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionProfile {
+    /// Number of tokens, not counting spaces.
+    pub token_count: usize,
+    pub op_count: usize,
+    pub max_stack_depth: usize,
+    /// `op_count + max_stack_depth / 2`, a rough measure of the work and
+    /// memory evaluation needs.
+    pub complexity_score: f64,
+}
+
+/// Describes `input` without evaluating it, e.g. to reject expressions that
+/// are too large before running them.
+pub fn expression_profile(input: &str) -> Result<ExpressionProfile, TokenError> {
+    let tokens = tokenize(input, TokenizerMode::Standard)?;
+
+    let token_count = tokens
+        .iter()
+        .filter(|(_, token)| *token != PolishNotationToken::Space)
+        .count();
+    let op_count = tokens
+        .iter()
+        .filter(|(_, token)| matches!(token, PolishNotationToken::Operation(_)))
+        .count();
+    let max_stack_depth = max_stack_depth(&tokens);
+
+    Ok(ExpressionProfile {
+        token_count,
+        op_count,
+        max_stack_depth,
+        complexity_score: op_count as f64 + max_stack_depth as f64 / 2.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_simple_expression() {
+        assert_eq!(
+            expression_profile("3 5 + 2 *"),
+            Ok(ExpressionProfile {
+                token_count: 5,
+                op_count: 2,
+                max_stack_depth: 2,
+                complexity_score: 3.0,
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_whitespace() {
+        assert_eq!(
+            expression_profile("35+2*"),
+            expression_profile(" 3 5 +  2 * ")
+        );
+    }
+
+    #[test]
+    fn deep_expressions_score_higher() {
+        let flat = expression_profile("1 2 + 3 + 4 +").unwrap();
+        let deep = expression_profile("1 2 3 4 + + +").unwrap();
+
+        assert_eq!(flat.max_stack_depth, 2);
+        assert_eq!(deep.max_stack_depth, 4);
+        assert!(deep.complexity_score > flat.complexity_score);
+    }
+
+    #[test]
+    fn profiles_without_evaluating() {
+        let profile = expression_profile("3 0 /").unwrap();
+
+        assert_eq!(profile.op_count, 1);
+        assert_eq!(profile.token_count, 3);
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(
+            expression_profile(""),
+            Ok(ExpressionProfile {
+                token_count: 0,
+                op_count: 0,
+                max_stack_depth: 0,
+                complexity_score: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(
+            expression_profile("3 a"),
+            Err(TokenError::InvalidCharacter(2, 'a'))
+        );
+    }
+}
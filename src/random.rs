@@ -0,0 +1,126 @@
+use rand::Rng;
+
+use crate::{OperationType, PolishNotationToken};
+
+/// Generates a random expression that evaluates without error.
+///
+/// Only single-digit numbers and addition, subtraction and multiplication
+/// are used, and each operation is checked against the values of its
+/// operands so the result never underflows or overflows a `u32`.
+pub fn random_expression(depth: usize, rng: &mut impl Rng) -> Vec<PolishNotationToken> {
+    let mut tokens = Vec::new();
+    push_random_subexpression(depth, rng, &mut tokens);
+    tokens
+}
+
+fn push_random_subexpression(
+    depth: usize,
+    rng: &mut impl Rng,
+    tokens: &mut Vec<PolishNotationToken>,
+) -> u32 {
+    if depth == 0 || rng.random_bool(0.3) {
+        let num = rng.random_range(0..=9);
+        tokens.push(PolishNotationToken::Number(num));
+        return num;
+    }
+
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    let x = push_random_subexpression(depth - 1, rng, &mut left);
+    let y = push_random_subexpression(depth - 1, rng, &mut right);
+
+    // Subtraction always fits once the larger operand is placed first, so there is
+    // always at least one candidate.
+    let mut candidates = vec![OperationType::Subtraction];
+    if x.checked_add(y).is_some() {
+        candidates.push(OperationType::Addition);
+    }
+    if x.checked_mul(y).is_some() {
+        candidates.push(OperationType::Multiplication);
+    }
+
+    let op = candidates[rng.random_range(0..candidates.len())];
+    let (x, y, first, second) = if op == OperationType::Subtraction && x < y {
+        (y, x, right, left)
+    } else {
+        (x, y, left, right)
+    };
+
+    tokens.extend(first);
+    tokens.push(PolishNotationToken::Space);
+    tokens.extend(second);
+    tokens.push(PolishNotationToken::Space);
+    tokens.push(PolishNotationToken::Operation(op));
+
+    match op {
+        OperationType::Addition => x + y,
+        OperationType::Subtraction => x - y,
+        OperationType::Multiplication => x * y,
+        OperationType::Division => unreachable!("division is never generated"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::calculate_rpd;
+
+    #[test]
+    fn depth_zero_is_a_single_digit() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let tokens = random_expression(0, &mut rng);
+            assert!(matches!(
+                tokens.as_slice(),
+                [PolishNotationToken::Number(0..=9)]
+            ));
+        }
+    }
+
+    #[test]
+    fn expressions_always_evaluate() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for depth in 0..10 {
+            for _ in 0..200 {
+                let tokens = random_expression(depth, &mut rng);
+                assert!(calculate_rpd(tokens.iter().copied().enumerate()).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn never_uses_division() {
+        let mut rng = StdRng::seed_from_u64(2);
+
+        for _ in 0..200 {
+            let tokens = random_expression(6, &mut rng);
+            assert!(!tokens.contains(&PolishNotationToken::Operation(OperationType::Division)));
+        }
+    }
+
+    #[test]
+    fn numbers_stay_tokenizable() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..200 {
+            let tokens = random_expression(6, &mut rng);
+            assert!(tokens.iter().all(|token| match token {
+                PolishNotationToken::Number(num) => *num <= 9,
+                _ => true,
+            }));
+        }
+    }
+
+    #[test]
+    fn same_seed_same_expression() {
+        let first = random_expression(5, &mut StdRng::seed_from_u64(4));
+        let second = random_expression(5, &mut StdRng::seed_from_u64(4));
+
+        assert_eq!(first, second);
+    }
+}
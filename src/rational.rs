@@ -0,0 +1,202 @@
+use std::fmt::Display;
+
+use crate::{CalculationError, OperationType, PolishNotationToken, Stack};
+
+/// An exact fraction, kept in lowest terms with a positive denominator. It
+/// displays as `p/q`, or just `p` when it is a whole number.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rational {
+    pub num: i64,
+    pub den: i64,
+}
+
+impl Rational {
+    /// Reduces `num / den` to lowest terms. Returns `None` when `den` is zero
+    /// or the reduced fraction does not fit in `i64`.
+    pub fn new(num: i64, den: i64) -> Option<Self> {
+        Rational::reduce(num.into(), den.into())
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+
+    // Operands are widened to i128 first, so the cross products of two i64
+    // fractions cannot overflow before they are reduced.
+    fn reduce(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+
+        let gcd = gcd(num.unsigned_abs(), den.unsigned_abs()) as i128;
+        let sign = den.signum();
+
+        Some(Rational {
+            num: (sign * num / gcd).try_into().ok()?,
+            den: (sign * den / gcd).try_into().ok()?,
+        })
+    }
+
+    fn compute(
+        op: OperationType,
+        op_pos: usize,
+        x: Rational,
+        y: Rational,
+    ) -> Result<Rational, CalculationError> {
+        let (a, b) = (i128::from(x.num), i128::from(x.den));
+        let (c, d) = (i128::from(y.num), i128::from(y.den));
+
+        let (num, den) = match op {
+            OperationType::Addition => (a * d + c * b, b * d),
+            OperationType::Subtraction => (a * d - c * b, b * d),
+            OperationType::Multiplication => (a * c, b * d),
+            OperationType::Division if c == 0 => {
+                return Err(CalculationError::DivisionByZero(op_pos))
+            }
+            OperationType::Division => (a * d, b * c),
+        };
+
+        Rational::reduce(num, den).ok_or(CalculationError::Overflow(op_pos, op))
+    }
+}
+
+impl From<u32> for Rational {
+    fn from(num: u32) -> Self {
+        Rational {
+            num: num.into(),
+            den: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.den {
+            1 => write!(f, "{}", self.num),
+            den => write!(f, "{}/{}", self.num, den),
+        }
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Evaluates tokens exactly, keeping every stack value as a [`Rational`].
+///
+/// Division no longer truncates and subtraction may go below zero, so
+/// `1 3 /` gives `1/3` and `1 2 -` gives `-1`.
+pub fn evaluate_rational(
+    tokens: impl IntoIterator<Item = (usize, PolishNotationToken)>,
+) -> Result<Rational, CalculationError> {
+    let mut stack = Stack::<Rational>::new();
+
+    for (pos, token) in tokens {
+        match token {
+            PolishNotationToken::Operation(op) => {
+                let y = stack
+                    .pop()
+                    .ok_or(CalculationError::NoNumberFoundForOperation(pos, op))?;
+                let x = stack
+                    .pop()
+                    .ok_or(CalculationError::NoNumberFoundForOperation(pos, op))?;
+
+                stack.push(Rational::compute(op, pos, x, y)?);
+            }
+            PolishNotationToken::Number(num) => stack.push(Rational::from(num)),
+            PolishNotationToken::Space => continue,
+        }
+    }
+
+    if stack.len() > 1 {
+        return Err(CalculationError::IncompleteExpression(stack.len()));
+    }
+
+    stack.pop().ok_or(CalculationError::NoResultAvailable(
+        "No result can be generated.",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tokenize, TokenizerMode};
+
+    fn evaluate(input: &str) -> Result<Rational, CalculationError> {
+        evaluate_rational(tokenize(input, TokenizerMode::Standard).unwrap())
+    }
+
+    #[test]
+    fn keeps_fractions_exact() {
+        assert_eq!(evaluate("1 3 /"), Ok(Rational { num: 1, den: 3 }));
+        assert_eq!(evaluate("1 3 / 1 3 / +"), Ok(Rational { num: 2, den: 3 }));
+        assert_eq!(evaluate("1 2 -"), Ok(Rational { num: -1, den: 1 }));
+        assert_eq!(evaluate("3 5 + 2 *"), Ok(Rational::from(16)));
+    }
+
+    #[test]
+    fn reduces_to_lowest_terms() {
+        assert_eq!(evaluate("2 4 /"), Ok(Rational { num: 1, den: 2 }));
+        assert_eq!(Rational::new(2, -4), Some(Rational { num: -1, den: 2 }));
+        assert_eq!(Rational::new(0, 5), Some(Rational { num: 0, den: 1 }));
+        assert_eq!(Rational::new(1, 0), None);
+        assert_eq!(Rational::new(i64::MIN, -1), None);
+    }
+
+    #[test]
+    fn displays_as_fraction() {
+        assert_eq!(evaluate("1 3 /").unwrap().to_string(), "1/3");
+        assert_eq!(evaluate("1 2 3 / -").unwrap().to_string(), "1/3");
+        assert_eq!(evaluate("4 2 /").unwrap().to_string(), "2");
+        assert_eq!(Rational { num: -1, den: 2 }.to_string(), "-1/2");
+    }
+
+    #[test]
+    fn converts_to_f64() {
+        assert_eq!(Rational { num: 1, den: 4 }.to_f64(), 0.25);
+        assert_eq!(Rational { num: -3, den: 2 }.to_f64(), -1.5);
+    }
+
+    #[test]
+    fn reports_arithmetic_errors() {
+        assert_eq!(evaluate("3 0 /"), Err(CalculationError::DivisionByZero(4)));
+        assert_eq!(
+            evaluate("1 1 1 - /"),
+            Err(CalculationError::DivisionByZero(8))
+        );
+
+        // 9^20 is the first power of nine past i64::MAX.
+        let input = format!("9{}", " 9 *".repeat(19));
+        assert_eq!(
+            evaluate(&input),
+            Err(CalculationError::Overflow(
+                76,
+                OperationType::Multiplication
+            ))
+        );
+    }
+
+    #[test]
+    fn reports_malformed_expressions() {
+        assert_eq!(
+            evaluate("3 +"),
+            Err(CalculationError::NoNumberFoundForOperation(
+                2,
+                OperationType::Addition
+            ))
+        );
+        assert_eq!(
+            evaluate("1 2"),
+            Err(CalculationError::IncompleteExpression(2))
+        );
+        assert_eq!(
+            evaluate(""),
+            Err(CalculationError::NoResultAvailable(
+                "No result can be generated."
+            ))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::ops::Range;
+
+use crate::{PolishNotationToken, TokenError, Tokenizer, TokenizerMode};
+
+/// Byte ranges in the original input, one per token.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SourceMap(Vec<Range<usize>>);
+
+impl SourceMap {
+    /// Returns the byte range of the token at `token_index` in the token list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `token_index` is out of bounds.
+    pub fn span(&self, token_index: usize) -> Range<usize> {
+        self.0[token_index].clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Tokenizes like [`crate::tokenize`] and also records where each token
+/// came from in `input`.
+pub fn tokenize_with_source_map(
+    input: &str,
+    mode: TokenizerMode,
+) -> Result<(Vec<(usize, PolishNotationToken)>, SourceMap), TokenError> {
+    let mut tokenizer = Tokenizer::with_mode(mode);
+    let mut tokens = Vec::new();
+    let mut spans = Vec::new();
+
+    // Byte offset where the token currently being assembled began.
+    let mut token_start = 0;
+
+    for (offset, ch) in input.char_indices() {
+        if let Some(token) = tokenizer.feed(ch) {
+            tokens.push(token?);
+            spans.push(token_start..offset + ch.len_utf8());
+            token_start = offset + ch.len_utf8();
+        }
+    }
+
+    if let Some(token) = tokenizer.flush() {
+        tokens.push(token?);
+        spans.push(token_start..input.len());
+    }
+
+    Ok((tokens, SourceMap(spans)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenize;
+
+    #[test]
+    fn tokens_match_tokenize() {
+        let (tokens, _) = tokenize_with_source_map("3 5 +", TokenizerMode::Standard).unwrap();
+        assert_eq!(tokens, tokenize("3 5 +", TokenizerMode::Standard).unwrap());
+    }
+
+    #[test]
+    fn maps_each_token_to_its_byte_range() {
+        let (_, source_map) = tokenize_with_source_map("3 5 +", TokenizerMode::Standard).unwrap();
+
+        assert_eq!(source_map.len(), 5);
+        assert_eq!(source_map.span(0), 0..1);
+        assert_eq!(source_map.span(2), 2..3);
+        assert_eq!(source_map.span(4), 4..5);
+    }
+
+    #[test]
+    fn spans_slice_the_source() {
+        let input = "9  3 /";
+        let (tokens, source_map) =
+            tokenize_with_source_map(input, TokenizerMode::Standard).unwrap();
+
+        let operator = tokens
+            .iter()
+            .position(|(_, token)| matches!(token, PolishNotationToken::Operation(_)))
+            .unwrap();
+        assert_eq!(&input[source_map.span(operator)], "/");
+    }
+
+    #[test]
+    fn empty_input_has_empty_map() {
+        let (tokens, source_map) = tokenize_with_source_map("", TokenizerMode::Standard).unwrap();
+
+        assert!(tokens.is_empty());
+        assert!(source_map.is_empty());
+    }
+
+    #[test]
+    fn returns_token_errors() {
+        assert_eq!(
+            tokenize_with_source_map("3 ?", TokenizerMode::Standard),
+            Err(TokenError::InvalidCharacter(2, '?'))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn span_panics_out_of_bounds() {
+        let (_, source_map) = tokenize_with_source_map("3", TokenizerMode::Standard).unwrap();
+        source_map.span(1);
+    }
+}
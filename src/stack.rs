@@ -0,0 +1,124 @@
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Stack<T>(Vec<T>);
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Stack(Vec::with_capacity(capacity))
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.0.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the values from the bottom of the stack to the top.
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Iterates from the bottom of the stack to the top.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Stack(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pops_in_reverse_push_order() {
+        let mut stack = Stack::new();
+        stack.push(1);
+        stack.push(2);
+
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn peek_does_not_remove() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.peek(), None);
+
+        stack.push(7);
+        assert_eq!(stack.peek(), Some(&7));
+        assert_eq!(stack.len(), 1);
+    }
+
+    #[test]
+    fn tracks_length() {
+        let mut stack = Stack::new();
+        assert!(stack.is_empty());
+
+        stack.push('a');
+        stack.push('b');
+        assert_eq!(stack.len(), 2);
+        assert!(!stack.is_empty());
+    }
+
+    #[test]
+    fn iterates_bottom_to_top() {
+        let stack: Stack<u32> = [1, 2, 3].into_iter().collect();
+
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(stack.as_slice(), &[1, 2, 3]);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn collected_stack_pops_last_item_first() {
+        let mut stack: Stack<u32> = [1, 2, 3].into_iter().collect();
+
+        assert_eq!(stack.peek(), Some(&3));
+        assert_eq!(stack.pop(), Some(3));
+    }
+
+    #[test]
+    fn default_is_empty() {
+        assert_eq!(Stack::<u32>::default(), Stack::new());
+    }
+
+    #[test]
+    fn with_capacity_starts_empty() {
+        let stack = Stack::<u32>::with_capacity(8);
+
+        assert!(stack.is_empty());
+        assert_eq!(stack, Stack::new());
+    }
+}
@@ -0,0 +1,245 @@
+use std::io::{BufRead, ErrorKind};
+
+use crate::{PolishNotationToken, TokenError, Tokenizer, TokenizerMode};
+
+/// Lazily tokenizes everything `reader` produces, reading one byte at a time.
+///
+/// Positions are byte offsets into the stream, as in [`crate::tokenize`]. A character whose
+/// UTF-8 encoding is split across two reads of the underlying buffer is
+/// reassembled first. Invalid UTF-8 is reported as an invalid `U+FFFD`
+/// character. Line breaks separate tokens like spaces do, so a file can
+/// spread an expression over several lines. The iterator ends after the first
+/// read error.
+pub fn tokenize_streaming(
+    reader: impl BufRead,
+    mode: TokenizerMode,
+) -> impl Iterator<Item = Result<(usize, PolishNotationToken), TokenError>> {
+    TokenStream {
+        reader,
+        tokenizer: Tokenizer::with_mode(mode),
+        done: false,
+    }
+}
+
+struct TokenStream<R> {
+    reader: R,
+    tokenizer: Tokenizer,
+    done: bool,
+}
+
+impl<R: BufRead> TokenStream<R> {
+    fn peek_byte(&mut self) -> Result<Option<u8>, ErrorKind> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().copied()),
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.kind()),
+            }
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<Option<u8>, ErrorKind> {
+        let byte = self.peek_byte()?;
+        if byte.is_some() {
+            self.reader.consume(1);
+        }
+        Ok(byte)
+    }
+
+    // Returns the character along with the number of bytes it was read from.
+    fn read_char(&mut self) -> Result<Option<(char, usize)>, ErrorKind> {
+        let Some(first) = self.read_byte()? else {
+            return Ok(None);
+        };
+
+        let len = match first {
+            0x00..=0x7f => return Ok(Some((char::from(first), 1))),
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Ok(Some((char::REPLACEMENT_CHARACTER, 1))),
+        };
+
+        // Only continuation bytes belong to the character. Any other byte is
+        // left in the reader, so a truncated sequence does not swallow the
+        // character after it.
+        let mut bytes = [first, 0, 0, 0];
+        for (read, byte) in bytes.iter_mut().enumerate().take(len).skip(1) {
+            match self.peek_byte()? {
+                Some(next @ 0x80..=0xbf) => {
+                    self.reader.consume(1);
+                    *byte = next;
+                }
+                _ => return Ok(Some((char::REPLACEMENT_CHARACTER, read))),
+            }
+        }
+
+        let ch = std::str::from_utf8(&bytes[..len])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+
+        Ok(Some((ch, len)))
+    }
+}
+
+impl<R: BufRead> Iterator for TokenStream<R> {
+    type Item = Result<(usize, PolishNotationToken), TokenError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.read_char() {
+                Ok(Some((ch, len))) => {
+                    let ch = if matches!(ch, '\n' | '\r') { ' ' } else { ch };
+                    if let Some(token) = self.tokenizer.feed_with_len(ch, len) {
+                        return Some(token);
+                    }
+                }
+                Ok(None) => {
+                    self.done = true;
+                    return self.tokenizer.flush();
+                }
+                Err(kind) => {
+                    self.done = true;
+                    return Some(Err(TokenError::ReadError(self.tokenizer.position(), kind)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, BufReader, Read};
+
+    use crate::{calculate_rpd, tokenize, OperationType};
+
+    fn stream(data: &[u8]) -> Vec<Result<(usize, PolishNotationToken), TokenError>> {
+        tokenize_streaming(BufReader::with_capacity(1, data), TokenizerMode::Standard).collect()
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::from(io::ErrorKind::BrokenPipe))
+        }
+    }
+
+    #[test]
+    fn matches_tokenize() {
+        let expected: Vec<_> = tokenize("3 5 + 2 *", TokenizerMode::Standard)
+            .unwrap()
+            .into_iter()
+            .map(Ok)
+            .collect();
+        assert_eq!(stream(b"3 5 + 2 *"), expected);
+    }
+
+    #[test]
+    fn feeds_calculate_rpd_directly() {
+        let tokens =
+            tokenize_streaming(&b"3 5 +\n2 *\n"[..], TokenizerMode::Standard).map(Result::unwrap);
+        assert_eq!(calculate_rpd(tokens), Ok(16));
+    }
+
+    #[test]
+    fn reads_line_breaks_as_spaces() {
+        assert_eq!(
+            stream(b"3\r\n5"),
+            vec![
+                Ok((0, PolishNotationToken::Number(3))),
+                Ok((1, PolishNotationToken::Space)),
+                Ok((2, PolishNotationToken::Space)),
+                Ok((3, PolishNotationToken::Number(5))),
+            ]
+        );
+        assert_eq!(
+            stream(b"3\t5"),
+            vec![
+                Ok((0, PolishNotationToken::Number(3))),
+                Err(TokenError::InvalidCharacter(1, '\t')),
+                Ok((2, PolishNotationToken::Number(5))),
+            ]
+        );
+    }
+
+    #[test]
+    fn reassembles_characters_split_across_reads() {
+        assert_eq!(
+            stream("3é".as_bytes()),
+            vec![
+                Ok((0, PolishNotationToken::Number(3))),
+                Err(TokenError::InvalidCharacter(1, 'é')),
+            ]
+        );
+    }
+
+    #[test]
+    fn positions_are_byte_offsets() {
+        assert_eq!(
+            stream("é1".as_bytes()),
+            vec![
+                Err(TokenError::InvalidCharacter(0, 'é')),
+                Ok((2, PolishNotationToken::Number(1))),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_utf8_as_replacement_character() {
+        assert_eq!(
+            stream(&[0xff, b'1']),
+            vec![
+                Err(TokenError::InvalidCharacter(0, char::REPLACEMENT_CHARACTER)),
+                Ok((1, PolishNotationToken::Number(1))),
+            ]
+        );
+        assert_eq!(
+            stream(&[b'1', 0xc3]),
+            vec![
+                Ok((0, PolishNotationToken::Number(1))),
+                Err(TokenError::InvalidCharacter(1, char::REPLACEMENT_CHARACTER)),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_the_character_after_a_truncated_sequence() {
+        assert_eq!(
+            stream(&[0xc3, b'1', b'2', b'+']),
+            vec![
+                Err(TokenError::InvalidCharacter(0, char::REPLACEMENT_CHARACTER)),
+                Ok((1, PolishNotationToken::Number(1))),
+                Ok((2, PolishNotationToken::Number(2))),
+                Ok((3, PolishNotationToken::Operation(OperationType::Addition))),
+            ]
+        );
+        assert_eq!(
+            stream(&[0xe2, 0x82, b'1']),
+            vec![
+                Err(TokenError::InvalidCharacter(0, char::REPLACEMENT_CHARACTER)),
+                Ok((2, PolishNotationToken::Number(1))),
+            ]
+        );
+    }
+
+    #[test]
+    fn stops_after_read_error() {
+        let results: Vec<_> =
+            tokenize_streaming(BufReader::new(FailingReader), TokenizerMode::Standard).collect();
+
+        assert_eq!(
+            results,
+            vec![Err(TokenError::ReadError(0, io::ErrorKind::BrokenPipe))]
+        );
+    }
+
+    #[test]
+    fn empty_reader_yields_nothing() {
+        assert!(stream(b"").is_empty());
+    }
+}
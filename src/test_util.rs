@@ -0,0 +1,74 @@
+use crate::{OperationType, PolishNotationToken};
+
+pub(crate) const ADD: PolishNotationToken = PolishNotationToken::Operation(OperationType::Addition);
+pub(crate) const MUL: PolishNotationToken =
+    PolishNotationToken::Operation(OperationType::Multiplication);
+pub(crate) const DIV: PolishNotationToken = PolishNotationToken::Operation(OperationType::Division);
+
+pub(crate) fn num(n: u32) -> PolishNotationToken {
+    PolishNotationToken::Number(n)
+}
+
+/// Runs `f` and returns the name and `expression` field of every span it
+/// opened, along with the number of events it emitted.
+#[cfg(feature = "tracing")]
+pub(crate) fn record_spans(f: impl FnOnce()) -> (Vec<(&'static str, Option<String>)>, usize) {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Recorded {
+        spans: Vec<(&'static str, Option<String>)>,
+        events: usize,
+    }
+
+    struct Recorder(Arc<Mutex<Recorded>>);
+
+    struct ExpressionField(Option<String>);
+
+    impl Visit for ExpressionField {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "expression" {
+                self.0 = Some(String::from(value));
+            }
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut expression = ExpressionField(None);
+            span.record(&mut expression);
+
+            let mut recorded = self.0.lock().unwrap();
+            recorded.spans.push((span.metadata().name(), expression.0));
+            Id::from_u64(recorded.spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {
+            self.0.lock().unwrap().events += 1;
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    let recorded = Arc::new(Mutex::new(Recorded::default()));
+    tracing::subscriber::with_default(Recorder(Arc::clone(&recorded)), f);
+
+    let recorded = std::mem::take(&mut *recorded.lock().unwrap());
+    (recorded.spans, recorded.events)
+}
@@ -0,0 +1,210 @@
+use crate::{OperationType, PolishNotationToken, TokenError};
+
+/// Turns characters into tokens one at a time.
+///
+/// Every token is currently a single character, so [`Tokenizer::feed`]
+/// emits a token for each character and [`Tokenizer::flush`] has nothing
+/// left to emit. Callers should still flush at the end of their input so
+/// they keep working once multi-character tokens are buffered.
+///
+/// Positions are byte offsets into the input, so they can index the source
+/// string directly.
+#[derive(Debug, Default, Clone)]
+pub struct Tokenizer {
+    position: usize,
+    mode: TokenizerMode,
+}
+
+/// Which spellings the tokenizer accepts. Every function that tokenizes a
+/// whole input takes one, so callers pick the dialect the same way
+/// everywhere.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TokenizerMode {
+    /// Also accepts `x` and `X` as multiplication.
+    #[default]
+    Standard,
+    /// Accepts only `+ - * /`, digits and the space character.
+    Strict,
+}
+
+impl Tokenizer {
+    pub fn new() -> Self {
+        Tokenizer::default()
+    }
+
+    pub fn with_mode(mode: TokenizerMode) -> Self {
+        Tokenizer { position: 0, mode }
+    }
+
+    pub fn mode(&self) -> TokenizerMode {
+        self.mode
+    }
+
+    /// Switches dialect. Only characters fed in after the call are affected.
+    pub fn set_mode(&mut self, mode: TokenizerMode) {
+        self.mode = mode;
+    }
+
+    /// The byte offset the next character fed in will have.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn feed(&mut self, ch: char) -> Option<Result<(usize, PolishNotationToken), TokenError>> {
+        self.feed_with_len(ch, ch.len_utf8())
+    }
+
+    // Streams feed U+FFFD in place of malformed bytes, which can take up fewer
+    // bytes in the input than the character itself.
+    pub(crate) fn feed_with_len(
+        &mut self,
+        ch: char,
+        len: usize,
+    ) -> Option<Result<(usize, PolishNotationToken), TokenError>> {
+        let index = self.position;
+        self.position += len;
+
+        Some(parse_token(index, ch, self.mode))
+    }
+
+    pub fn flush(&mut self) -> Option<Result<(usize, PolishNotationToken), TokenError>> {
+        None
+    }
+}
+
+fn parse_token(
+    index: usize,
+    ch: char,
+    mode: TokenizerMode,
+) -> Result<(usize, PolishNotationToken), TokenError> {
+    match ch {
+        '0'..='9' => Ok((index, PolishNotationToken::Number(ch.to_digit(10).unwrap()))),
+        ' ' => Ok((index, PolishNotationToken::Space)),
+        'x' | 'X' if mode == TokenizerMode::Strict => Err(TokenError::InvalidCharacter(index, ch)),
+        _ => OperationType::try_from(ch)
+            .map(|op| (index, PolishNotationToken::Operation(op)))
+            .map_err(|_| TokenError::InvalidCharacter(index, ch)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(ch: char) -> Result<(usize, PolishNotationToken), TokenError> {
+        parse_token(0, ch, TokenizerMode::Standard)
+    }
+
+    #[test]
+    fn parses_each_operator_character() {
+        for (ch, op) in [
+            ('+', OperationType::Addition),
+            ('-', OperationType::Subtraction),
+            ('*', OperationType::Multiplication),
+            ('x', OperationType::Multiplication),
+            ('X', OperationType::Multiplication),
+            ('/', OperationType::Division),
+        ] {
+            assert_eq!(parse(ch), Ok((0, PolishNotationToken::Operation(op))));
+        }
+    }
+
+    #[test]
+    fn parses_each_digit() {
+        for (digit, ch) in ('0'..='9').enumerate() {
+            assert_eq!(
+                parse(ch),
+                Ok((0, PolishNotationToken::Number(digit as u32)))
+            );
+        }
+    }
+
+    #[test]
+    fn parses_space() {
+        assert_eq!(parse(' '), Ok((0, PolishNotationToken::Space)));
+    }
+
+    #[test]
+    fn rejects_other_characters() {
+        for ch in ['a', '%', '.', 'é', '\u{a0}', '\t', '\n', '\r'] {
+            assert_eq!(parse(ch), Err(TokenError::InvalidCharacter(0, ch)));
+        }
+    }
+
+    #[test]
+    fn feed_numbers_tokens_by_position() {
+        let mut tokenizer = Tokenizer::new();
+
+        assert_eq!(
+            tokenizer.feed('3'),
+            Some(Ok((0, PolishNotationToken::Number(3))))
+        );
+        assert_eq!(
+            tokenizer.feed('a'),
+            Some(Err(TokenError::InvalidCharacter(1, 'a')))
+        );
+        assert_eq!(
+            tokenizer.feed(' '),
+            Some(Ok((2, PolishNotationToken::Space)))
+        );
+        assert_eq!(tokenizer.position(), 3);
+        assert_eq!(
+            tokenizer.feed('é'),
+            Some(Err(TokenError::InvalidCharacter(3, 'é')))
+        );
+        assert_eq!(tokenizer.position(), 5);
+        assert_eq!(tokenizer.flush(), None);
+    }
+
+    #[test]
+    fn strict_mode_rejects_aliases_and_other_whitespace() {
+        let mut tokenizer = Tokenizer::with_mode(TokenizerMode::Strict);
+
+        assert_eq!(
+            tokenizer.feed('x'),
+            Some(Err(TokenError::InvalidCharacter(0, 'x')))
+        );
+        assert_eq!(
+            tokenizer.feed('X'),
+            Some(Err(TokenError::InvalidCharacter(1, 'X')))
+        );
+        assert_eq!(
+            tokenizer.feed('\t'),
+            Some(Err(TokenError::InvalidCharacter(2, '\t')))
+        );
+        assert_eq!(
+            tokenizer.feed(' '),
+            Some(Ok((3, PolishNotationToken::Space)))
+        );
+        assert_eq!(
+            tokenizer.feed('*'),
+            Some(Ok((
+                4,
+                PolishNotationToken::Operation(OperationType::Multiplication)
+            )))
+        );
+    }
+
+    #[test]
+    fn set_mode_only_affects_later_characters() {
+        let mut tokenizer = Tokenizer::new();
+
+        assert_eq!(
+            tokenizer.feed('x'),
+            Some(Ok((
+                0,
+                PolishNotationToken::Operation(OperationType::Multiplication)
+            )))
+        );
+
+        tokenizer.set_mode(TokenizerMode::Strict);
+        assert_eq!(tokenizer.mode(), TokenizerMode::Strict);
+        assert_eq!(
+            tokenizer.feed('x'),
+            Some(Err(TokenError::InvalidCharacter(1, 'x')))
+        );
+
+        tokenizer.set_mode(TokenizerMode::Standard);
+        assert!(matches!(tokenizer.feed('x'), Some(Ok(_))));
+    }
+}
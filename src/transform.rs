@@ -0,0 +1,108 @@
+use crate::PolishNotationToken;
+
+/// Replaces every token with `f(token)`, keeping the original positions.
+pub fn tokens_apply_map(
+    tokens: &[(usize, PolishNotationToken)],
+    f: impl Fn(&PolishNotationToken) -> PolishNotationToken,
+) -> Vec<(usize, PolishNotationToken)> {
+    tokens.iter().map(|(pos, token)| (*pos, f(token))).collect()
+}
+
+/// Keeps the tokens for which `pred` returns `true`, along with their
+/// original positions.
+pub fn tokens_filter(
+    tokens: &[(usize, PolishNotationToken)],
+    pred: impl Fn(&PolishNotationToken) -> bool,
+) -> Vec<(usize, PolishNotationToken)> {
+    tokens
+        .iter()
+        .filter(|(_, token)| pred(token))
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_rpd, tokenize, OperationType, TokenizerMode};
+
+    fn substitute(
+        tokens: &[(usize, PolishNotationToken)],
+        from: PolishNotationToken,
+        to: PolishNotationToken,
+    ) -> Vec<(usize, PolishNotationToken)> {
+        tokens_apply_map(tokens, |token| if *token == from { to } else { *token })
+    }
+
+    #[test]
+    fn map_keeps_positions() {
+        let tokens = tokenize("3 5 +", TokenizerMode::Standard).unwrap();
+        let mapped = tokens_apply_map(&tokens, |_| PolishNotationToken::Space);
+
+        assert_eq!(
+            mapped.iter().map(|(pos, _)| *pos).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn map_substitutes_operations() {
+        let tokens = tokenize("3 5 +", TokenizerMode::Standard).unwrap();
+        let multiplied = substitute(
+            &tokens,
+            PolishNotationToken::Operation(OperationType::Addition),
+            PolishNotationToken::Operation(OperationType::Multiplication),
+        );
+
+        assert_eq!(calculate_rpd(multiplied), Ok(15));
+    }
+
+    #[test]
+    fn map_scales_numbers() {
+        let tokens = tokenize("3 5 +", TokenizerMode::Standard).unwrap();
+        let scaled = tokens_apply_map(&tokens, |token| match token {
+            PolishNotationToken::Number(num) => PolishNotationToken::Number(num * 10),
+            other => *other,
+        });
+
+        assert_eq!(calculate_rpd(scaled), Ok(80));
+    }
+
+    #[test]
+    fn filter_strips_spaces() {
+        let tokens = tokenize("3 5 +", TokenizerMode::Standard).unwrap();
+        let stripped = tokens_filter(&tokens, |token| *token != PolishNotationToken::Space);
+
+        assert_eq!(
+            stripped,
+            vec![
+                (0, PolishNotationToken::Number(3)),
+                (2, PolishNotationToken::Number(5)),
+                (4, PolishNotationToken::Operation(OperationType::Addition)),
+            ]
+        );
+    }
+
+    #[test]
+    fn filter_extracts_literals() {
+        let tokens = tokenize("3 5 + 2 *", TokenizerMode::Standard).unwrap();
+        let literals = tokens_filter(&tokens, |token| {
+            matches!(token, PolishNotationToken::Number(_))
+        });
+
+        assert_eq!(
+            literals,
+            vec![
+                (0, PolishNotationToken::Number(3)),
+                (2, PolishNotationToken::Number(5)),
+                (6, PolishNotationToken::Number(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_lists_stay_empty() {
+        assert!(tokens_apply_map(&[], |token| *token).is_empty());
+        assert!(tokens_filter(&[], |_| true).is_empty());
+    }
+}